@@ -0,0 +1,474 @@
+//!
+//! derive macros for the ToDocValue and FromDocValue traits of gzbbinarydoc,
+//! use them through the derive feature of gzbbinarydoc. deriving ToDocValue
+//! also implements TryToDocValue so derived types work with try_insert.
+//!
+//! structs with named fields become objects, newtype structs the value they
+//! wrap, tuple structs vecs and unit structs null. unit enum variants become
+//! a string holding the variant name, the others a object holding the
+//! variant name as its only key.
+//!
+//! field attributes
+//!     #[docvalue(rename = "name")] - object key of the field or name of the variant
+//!     #[docvalue(skip)] - field is not written and read back as Default::default()
+//!     #[docvalue(default)] - Default::default() when the key is missing
+//!     #[docvalue(default = "path")] - calls path() when the key is missing
+//!     #[docvalue(flatten)] - entries of the field object are merged into the parent object
+//!
+//! rename or flatten on a tuple field and any docvalue attribute on the
+//! type itself are compile errors instead of being ignored.
+//!
+//! a missing key that has no default is read from null, so Option fields
+//! become None and other fields fail.
+//!
+
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as Tokens,Span};
+use quote::quote;
+use syn::{parse_macro_input,parse_quote,Attribute,Data,DeriveInput,Error,Fields,Generics,Ident,LitStr,Path};
+
+#[proc_macro_derive(ToDocValue,attributes(docvalue))]
+pub fn derive_to_doc_value(input:TokenStream)->TokenStream{
+    let input = parse_macro_input!(input as DeriveInput);
+    match to_doc_value(&input){
+        Ok(v)=>{v.into()},
+        Err(e)=>{e.to_compile_error().into()}
+    }
+}
+
+#[proc_macro_derive(FromDocValue,attributes(docvalue))]
+pub fn derive_from_doc_value(input:TokenStream)->TokenStream{
+    let input = parse_macro_input!(input as DeriveInput);
+    match from_doc_value(&input){
+        Ok(v)=>{v.into()},
+        Err(e)=>{e.to_compile_error().into()}
+    }
+}
+
+//attributes
+
+#[derive(Default)]
+struct Attrs{
+    rename:Option<String>,
+    skip:bool,
+    default:Option<Option<Path>>,
+    flatten:bool,
+}
+
+impl Attrs{
+    fn parse(attrs:&[Attribute])->syn::Result<Attrs>{
+        let mut build = Attrs::default();
+        for attr in attrs{
+            if !attr.path().is_ident("docvalue"){continue;}
+            attr.parse_nested_meta(|meta|{
+                if meta.path.is_ident("rename"){
+                    let value:LitStr = meta.value()?.parse()?;
+                    build.rename = Some(value.value());
+                } else if meta.path.is_ident("skip"){
+                    build.skip = true;
+                } else if meta.path.is_ident("default"){
+                    if meta.input.peek(syn::Token![=]){
+                        let value:LitStr = meta.value()?.parse()?;
+                        build.default = Some(Some(value.parse()?));
+                    } else {
+                        build.default = Some(None);
+                    }
+                } else if meta.path.is_ident("flatten"){
+                    build.flatten = true;
+                } else {
+                    return Err(meta.error("unknown docvalue attribute, expected rename, skip, default or flatten"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(build)
+    }
+    ///object key or variant name
+    fn key(&self,ident:&Ident)->String{
+        match &self.rename{
+            Some(v)=>{v.clone()},
+            None=>{ident.to_string().trim_start_matches("r#").to_string()}
+        }
+    }
+    ///expression used for skipped fields and missing keys with a default
+    fn default_value(&self)->Tokens{
+        match &self.default{
+            Some(Some(path))=>{quote!{#path()}},
+            _=>{quote!{::std::default::Default::default()}}
+        }
+    }
+}
+
+///docvalue attributes are read from fields and variants only
+fn container_attrs(input:&DeriveInput)->syn::Result<()>{
+    for attr in input.attrs.iter(){
+        if attr.path().is_ident("docvalue"){
+            return Err(Error::new_spanned(attr,"docvalue attributes go on fields and enum variants, not on the type"));
+        }
+    }
+    Ok(())
+}
+
+///attributes of a tuple field, which has no key to rename or flatten into
+fn tuple_field_attrs(field:&syn::Field)->syn::Result<Attrs>{
+    let attrs = Attrs::parse(&field.attrs)?;
+    if attrs.rename.is_some() || attrs.flatten{
+        return Err(Error::new_spanned(field,"only skip and default are supported on tuple fields"));
+    }
+    Ok(attrs)
+}
+
+///adds bound to every type parameter
+fn bounded(generics:&Generics,bound:Tokens)->Generics{
+    let mut build = generics.clone();
+    for param in build.type_params_mut(){
+        param.bounds.push(parse_quote!(#bound));
+    }
+    build
+}
+
+fn binding(index:usize)->Ident{
+    Ident::new(&format!("field_{}",index),Span::call_site())
+}
+
+///local of the generated code, mixed site so it never shadows a field binding
+fn local(name:&str)->Ident{
+    Ident::new(name,Span::mixed_site())
+}
+
+//to doc value
+
+fn to_doc_value(input:&DeriveInput)->syn::Result<Tokens>{
+
+    container_attrs(input)?;
+
+    let name = &input.ident;
+    let generics = bounded(&input.generics,quote!{::gzbbinarydoc::ToDocValue});
+    let (impl_generics,ty_generics,where_clause) = generics.split_for_impl();
+
+    let body = match &input.data{
+        Data::Struct(data)=>{
+            match &data.fields{
+                Fields::Named(fields)=>{
+                    let mut values = vec![];
+                    for field in fields.named.iter(){
+                        let ident = field.ident.as_ref().unwrap();
+                        values.push((Attrs::parse(&field.attrs)?,ident.clone(),quote!{&self.#ident}));
+                    }
+                    object_value(&values)
+                },
+                Fields::Unnamed(fields)=>{
+                    let mut values = vec![];
+                    for (index,field) in fields.unnamed.iter().enumerate(){
+                        let index = syn::Index::from(index);
+                        values.push((tuple_field_attrs(field)?,quote!{&self.#index}));
+                    }
+                    if values.len() == 1 && !values[0].0.skip{
+                        let access = &values[0].1;
+                        quote!{::gzbbinarydoc::ToDocValue::toDocValue(#access)}
+                    } else {
+                        vec_value(&values)
+                    }
+                },
+                Fields::Unit=>{quote!{::gzbbinarydoc::DocValue::Null}}
+            }
+        },
+        Data::Enum(data)=>{
+            let mut arms = vec![];
+            for variant in data.variants.iter(){
+                let attrs = Attrs::parse(&variant.attrs)?;
+                if attrs.skip || attrs.flatten || attrs.default.is_some(){
+                    return Err(Error::new_spanned(variant,"only rename is supported on enum variants"));
+                }
+                let ident = &variant.ident;
+                let key = attrs.key(ident);
+                let arm = match &variant.fields{
+                    Fields::Named(fields)=>{
+                        let mut values = vec![];
+                        let mut bindings = vec![];
+                        for field in fields.named.iter(){
+                            let field_ident = field.ident.as_ref().unwrap();
+                            let field_attrs = Attrs::parse(&field.attrs)?;
+                            if field_attrs.skip{
+                                bindings.push(quote!{#field_ident:_});
+                            } else {
+                                bindings.push(quote!{#field_ident});
+                            }
+                            values.push((field_attrs,field_ident.clone(),quote!{#field_ident}));
+                        }
+                        let inner = object_value(&values);
+                        let wrapped = variant_value(&key,inner);
+                        quote!{#name::#ident{#(#bindings),*}=>{#wrapped}}
+                    },
+                    Fields::Unnamed(fields)=>{
+                        let mut values = vec![];
+                        let mut bindings = vec![];
+                        for (index,field) in fields.unnamed.iter().enumerate(){
+                            let binding = binding(index);
+                            let field_attrs = tuple_field_attrs(field)?;
+                            if field_attrs.skip{
+                                bindings.push(quote!{_});
+                            } else {
+                                bindings.push(quote!{#binding});
+                            }
+                            values.push((field_attrs,quote!{#binding}));
+                        }
+                        let inner = if values.len() == 1 && !values[0].0.skip{
+                            let access = &values[0].1;
+                            quote!{::gzbbinarydoc::ToDocValue::toDocValue(#access)}
+                        } else {
+                            vec_value(&values)
+                        };
+                        let wrapped = variant_value(&key,inner);
+                        quote!{#name::#ident(#(#bindings),*)=>{#wrapped}}
+                    },
+                    Fields::Unit=>{
+                        quote!{#name::#ident=>{::gzbbinarydoc::DocValue::String(<::std::string::String as ::std::convert::From<&str>>::from(#key))}}
+                    }
+                };
+                arms.push(arm);
+            }
+            if arms.is_empty(){
+                quote!{match *self{}}
+            } else {
+                quote!{match self{#(#arms),*}}
+            }
+        },
+        Data::Union(_)=>{
+            return Err(Error::new_spanned(input,"ToDocValue can not be derived for unions"));
+        }
+    };
+
+    Ok(quote!{
+        impl #impl_generics ::gzbbinarydoc::ToDocValue for #name #ty_generics #where_clause{
+            fn toDocValue(&self)->::gzbbinarydoc::DocValue{
+                #body
+            }
+        }
+        impl #impl_generics ::gzbbinarydoc::TryToDocValue for #name #ty_generics #where_clause{
+            fn tryToDocValue(&self)->::std::result::Result<::gzbbinarydoc::DocValue,::gzbbinarydoc::ToDocError>{
+                ::std::result::Result::Ok(::gzbbinarydoc::ToDocValue::toDocValue(self))
+            }
+        }
+    })
+
+}
+
+///object built from (attributes, field name, reference to the field value)
+fn object_value(values:&[(Attrs,Ident,Tokens)])->Tokens{
+    let map = local("map");
+    let inner = local("inner");
+    let other = local("other");
+    let mut inserts = vec![];
+    for (attrs,ident,access) in values{
+        if attrs.skip{continue;}
+        let key = attrs.key(ident);
+        if attrs.flatten{
+            inserts.push(quote!{
+                match ::gzbbinarydoc::ToDocValue::toDocValue(#access){
+                    ::gzbbinarydoc::DocValue::Object(#inner)=>{::std::iter::Extend::extend(&mut #map,#inner);},
+                    #other=>{#map.insert(<::std::string::String as ::std::convert::From<&str>>::from(#key),#other);}
+                }
+            });
+        } else {
+            inserts.push(quote!{
+                #map.insert(<::std::string::String as ::std::convert::From<&str>>::from(#key),::gzbbinarydoc::ToDocValue::toDocValue(#access));
+            });
+        }
+    }
+    quote!{
+        #[allow(unused_mut)]
+        let mut #map = ::gzbbinarydoc::Map::new();
+        #(#inserts)*
+        ::gzbbinarydoc::DocValue::Object(#map)
+    }
+}
+
+///vec built from (attributes, reference to the field value)
+fn vec_value(values:&[(Attrs,Tokens)])->Tokens{
+    let mut pushes = vec![];
+    for (attrs,access) in values{
+        if attrs.skip{continue;}
+        pushes.push(quote!{::gzbbinarydoc::ToDocValue::toDocValue(#access)});
+    }
+    quote!{::gzbbinarydoc::DocValue::Vec(::std::vec![#(#pushes),*])}
+}
+
+///object holding a enum variant as its only key
+fn variant_value(key:&str,value:Tokens)->Tokens{
+    let inner = local("inner");
+    let variant = local("variant");
+    quote!{
+        let #inner = {#value};
+        let mut #variant = ::gzbbinarydoc::Map::new();
+        #variant.insert(<::std::string::String as ::std::convert::From<&str>>::from(#key),#inner);
+        ::gzbbinarydoc::DocValue::Object(#variant)
+    }
+}
+
+//from doc value
+
+fn from_doc_value(input:&DeriveInput)->syn::Result<Tokens>{
+
+    container_attrs(input)?;
+
+    let name = &input.ident;
+    let generics = bounded(&input.generics,quote!{::gzbbinarydoc::FromDocValue});
+    let (impl_generics,ty_generics,where_clause) = generics.split_for_impl();
+
+    let body = match &input.data{
+        Data::Struct(data)=>{
+            read_fields(quote!{#name},&data.fields,quote!{value},&quote!{})?
+        },
+        Data::Enum(data)=>{
+            let expected = format!("variant of {}",name);
+            let mut unit_arms = vec![];
+            let mut object_arms = vec![];
+            for variant in data.variants.iter(){
+                let attrs = Attrs::parse(&variant.attrs)?;
+                if attrs.skip || attrs.flatten || attrs.default.is_some(){
+                    return Err(Error::new_spanned(variant,"only rename is supported on enum variants"));
+                }
+                let ident = &variant.ident;
+                let key = attrs.key(ident);
+                match &variant.fields{
+                    Fields::Unit=>{
+                        unit_arms.push(quote!{#key=>{::std::result::Result::Ok(#name::#ident)}});
+                    },
+                    fields=>{
+                        let read = read_fields(quote!{#name::#ident},fields,quote!{inner},&quote!{.with_key(#key)})?;
+                        object_arms.push(quote!{#key=>{#read}});
+                    }
+                }
+            }
+            quote!{
+                match value{
+                    ::gzbbinarydoc::DocValue::String(variant)=>{
+                        match variant.as_str(){
+                            #(#unit_arms,)*
+                            _=>{::std::result::Result::Err(::gzbbinarydoc::FromDocError::new(#expected,&::std::format!("variant {:?}",variant)))}
+                        }
+                    },
+                    ::gzbbinarydoc::DocValue::Object(map) if map.len() == 1=>{
+                        let (variant,inner) = ::std::iter::Iterator::next(&mut map.iter()).unwrap();
+                        #[allow(unused_variables)]
+                        let inner = inner;
+                        match variant.as_str(){
+                            #(#object_arms,)*
+                            _=>{::std::result::Result::Err(::gzbbinarydoc::FromDocError::new(#expected,&::std::format!("variant {:?}",variant)))}
+                        }
+                    },
+                    _=>{::std::result::Result::Err(::gzbbinarydoc::FromDocError::invalid_type(#expected,value))}
+                }
+            }
+        },
+        Data::Union(_)=>{
+            return Err(Error::new_spanned(input,"FromDocValue can not be derived for unions"));
+        }
+    };
+
+    Ok(quote!{
+        impl #impl_generics ::gzbbinarydoc::FromDocValue for #name #ty_generics #where_clause{
+            fn fromDocValue(value:&::gzbbinarydoc::DocValue)->::std::result::Result<Self,::gzbbinarydoc::FromDocError>{
+                #body
+            }
+        }
+    })
+
+}
+
+///
+/// expression reading fields from the DocValue named by value and building
+/// them with constructor, suffix is appended to every error so enum
+/// variants can prefix the path with their key.
+///
+fn read_fields(constructor:Tokens,fields:&Fields,value:Tokens,suffix:&Tokens)->syn::Result<Tokens>{
+    match fields{
+        Fields::Named(fields)=>{
+            let mut inits = vec![];
+            for field in fields.named.iter(){
+                let attrs = Attrs::parse(&field.attrs)?;
+                let ident = field.ident.as_ref().unwrap();
+                let key = attrs.key(ident);
+                let init = if attrs.skip{
+                    attrs.default_value()
+                } else if attrs.flatten{
+                    quote!{::gzbbinarydoc::FromDocValue::fromDocValue(#value).map_err(|e|e #suffix)?}
+                } else {
+                    let missing = match attrs.default{
+                        Some(_)=>{attrs.default_value()},
+                        None=>{quote!{
+                            match ::gzbbinarydoc::FromDocValue::fromDocValue(&::gzbbinarydoc::DocValue::Null){
+                                ::std::result::Result::Ok(v)=>{v},
+                                ::std::result::Result::Err(e)=>{return ::std::result::Result::Err(::gzbbinarydoc::FromDocError::missing(e.expected()).with_key(#key) #suffix);}
+                            }
+                        }}
+                    };
+                    quote!{
+                        match map.get(#key){
+                            ::std::option::Option::Some(v)=>{::gzbbinarydoc::FromDocValue::fromDocValue(v).map_err(|e|e.with_key(#key) #suffix)?},
+                            ::std::option::Option::None=>{#missing}
+                        }
+                    }
+                };
+                inits.push(quote!{#ident:#init});
+            }
+            Ok(quote!{
+                #[allow(unused_variables)]
+                let map = match #value{
+                    ::gzbbinarydoc::DocValue::Object(map)=>{map},
+                    _=>{return ::std::result::Result::Err(::gzbbinarydoc::FromDocError::invalid_type("object",#value) #suffix);}
+                };
+                ::std::result::Result::Ok(#constructor{#(#inits),*})
+            })
+        },
+        Fields::Unnamed(fields)=>{
+            let attrs = fields.unnamed.iter().map(tuple_field_attrs).collect::<syn::Result<Vec<Attrs>>>()?;
+            if attrs.len() == 1 && !attrs[0].skip{
+                return Ok(quote!{
+                    ::std::result::Result::Ok(#constructor(::gzbbinarydoc::FromDocValue::fromDocValue(#value).map_err(|e|e #suffix)?))
+                });
+            }
+            let mut inits = vec![];
+            let mut index:usize = 0;
+            for attr in attrs.iter(){
+                if attr.skip{
+                    inits.push(attr.default_value());
+                    continue;
+                }
+                let missing = match attr.default{
+                    Some(_)=>{attr.default_value()},
+                    None=>{quote!{
+                        match ::gzbbinarydoc::FromDocValue::fromDocValue(&::gzbbinarydoc::DocValue::Null){
+                            ::std::result::Result::Ok(v)=>{v},
+                            ::std::result::Result::Err(e)=>{return ::std::result::Result::Err(::gzbbinarydoc::FromDocError::missing(e.expected()).with_index(#index) #suffix);}
+                        }
+                    }}
+                };
+                inits.push(quote!{
+                    match pool.get(#index){
+                        ::std::option::Option::Some(v)=>{::gzbbinarydoc::FromDocValue::fromDocValue(v).map_err(|e|e.with_index(#index) #suffix)?},
+                        ::std::option::Option::None=>{#missing}
+                    }
+                });
+                index += 1;
+            }
+            Ok(quote!{
+                #[allow(unused_variables)]
+                let pool = match #value{
+                    ::gzbbinarydoc::DocValue::Vec(pool)=>{pool},
+                    _=>{return ::std::result::Result::Err(::gzbbinarydoc::FromDocError::invalid_type("vec",#value) #suffix);}
+                };
+                ::std::result::Result::Ok(#constructor(#(#inits),*))
+            })
+        },
+        Fields::Unit=>{
+            Ok(quote!{
+                match #value{
+                    ::gzbbinarydoc::DocValue::Null=>{::std::result::Result::Ok(#constructor)},
+                    _=>{::std::result::Result::Err(::gzbbinarydoc::FromDocError::invalid_type("null",#value) #suffix)}
+                }
+            })
+        }
+    }
+}
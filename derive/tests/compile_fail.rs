@@ -0,0 +1,5 @@
+#[test]
+fn rejected_attributes(){
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
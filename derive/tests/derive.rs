@@ -0,0 +1,282 @@
+use gzbbinarydoc::{DocValue,FromDocValue,FromDocError,ToDocValue};
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+struct Game{
+    game:String,
+    #[docvalue(rename = "match")]
+    game_match:i64,
+}
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+struct Meta{
+    version:i64,
+}
+
+fn one()->i64{
+    1
+}
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+struct Person{
+    name:String,
+    age:i64,
+    king:bool,
+    bin:Vec<u8>,
+    sports:Game,
+    #[docvalue(skip)]
+    cache:Vec<u8>,
+    #[docvalue(default)]
+    score:f64,
+    #[docvalue(default = "one")]
+    level:i64,
+    #[docvalue(flatten)]
+    meta:Meta,
+}
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+struct Id(i64);
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+struct Point(f64,f64);
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+struct Marker;
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+enum Shape{
+    Empty,
+    #[docvalue(rename = "circle")]
+    Circle(f64),
+    Line(Point,Point),
+    Rect{width:i64,#[docvalue(rename = "h")] height:i64},
+}
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+struct Wrapper<T>{
+    inner:T,
+}
+
+//fields named like the locals of the generated code
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+struct Locals{
+    map:String,
+    inner:i64,
+    variant:bool,
+    value:i64,
+    other:i64,
+    pool:i64,
+    #[docvalue(flatten)]
+    meta:Meta,
+}
+
+#[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+enum Scope{
+    Named{map:String,inner:i64,variant:bool,value:i64,other:i64},
+    Tuple(i64,String),
+}
+
+//derived code names the prelude by path so it builds without it
+#[no_implicit_prelude]
+mod no_prelude{
+    use ::gzbbinarydoc::{FromDocValue,ToDocValue};
+
+    #[derive(ToDocValue,FromDocValue,::std::fmt::Debug,::std::cmp::PartialEq)]
+    pub struct Plain{
+        pub name: ::std::string::String,
+        #[docvalue(default)]
+        pub nick: ::std::option::Option<::std::string::String>,
+        pub pair:Pair,
+        pub kind:Kind,
+        #[docvalue(flatten)]
+        pub extra:Extra,
+    }
+
+    #[derive(ToDocValue,FromDocValue,::std::fmt::Debug,::std::cmp::PartialEq)]
+    pub struct Extra{
+        pub version:i64,
+    }
+
+    #[derive(ToDocValue,FromDocValue,::std::fmt::Debug,::std::cmp::PartialEq)]
+    pub struct Pair(pub i64,#[docvalue(default)] pub bool);
+
+    #[derive(ToDocValue,FromDocValue,::std::fmt::Debug,::std::cmp::PartialEq)]
+    pub enum Kind{
+        Empty,
+        One(i64),
+        Two(i64,i64),
+        Named{x:i64},
+    }
+}
+
+//and with the prelude names shadowed by local items
+mod shadowed{
+    #![allow(dead_code,unused_macros)]
+    use gzbbinarydoc::{FromDocValue,ToDocValue};
+
+    pub struct Ok;
+    pub struct Err;
+    pub struct Some;
+    pub struct None;
+    pub type Result = ();
+    macro_rules! vec{()=>{}}
+    macro_rules! format{()=>{}}
+
+    #[derive(ToDocValue,FromDocValue,Debug,PartialEq)]
+    pub enum Kind{
+        Empty,
+        Two(i64,i64),
+        Named{x:i64,#[docvalue(default)] y:i64},
+    }
+}
+
+fn person()->Person{
+    Person{
+        name:"akku".to_string(),
+        age:24,
+        king:true,
+        bin:vec![1,2,3],
+        sports:Game{game:"cricket".to_string(),game_match:4},
+        cache:vec![9],
+        score:12.5,
+        level:3,
+        meta:Meta{version:2},
+    }
+}
+
+#[test]
+fn struct_layout(){
+    let doc = person().toDocValue();
+    let map = doc.as_object().unwrap();
+    assert_eq!(map.len(),8);
+    assert!(!map.contains_key("cache"));
+    assert!(map.contains_key("version"));
+    assert!(!map.contains_key("meta"));
+    assert!(map.get("sports").unwrap().key_is_num("match"));
+    assert!(doc.key_is_binary("bin"));
+}
+
+#[test]
+fn struct_round_trip(){
+    let mut doc = DocValue::object();
+    doc.insert("person",person());
+    let bin = doc.write();
+    let read = DocValue::read(&bin).unwrap();
+    let back = Person::fromDocValue(read.as_object().unwrap().get("person").unwrap()).unwrap();
+    assert_eq!(back,Person{cache:vec![],..person()});
+}
+
+#[test]
+fn missing_keys_use_defaults(){
+    let mut doc = person().toDocValue();
+    doc.object_remove("score");
+    doc.object_remove("level");
+    let back = Person::fromDocValue(&doc).unwrap();
+    assert_eq!(back.score,0.0);
+    assert_eq!(back.level,1);
+    doc.object_remove("age");
+    let error = Person::fromDocValue(&doc).unwrap_err();
+    assert_eq!(error.path(),"age");
+    assert_eq!(error.found(),"nothing");
+}
+
+#[test]
+fn tuple_unit_and_generic_structs(){
+    assert_eq!(Id(7).toDocValue().as_num().unwrap(),7);
+    assert_eq!(Id::fromDocValue(&DocValue::num(7)).unwrap(),Id(7));
+    let point = Point(1.5,2.5).toDocValue();
+    assert_eq!(point.as_vec().unwrap().len(),2);
+    assert_eq!(Point::fromDocValue(&point).unwrap(),Point(1.5,2.5));
+    assert!(Marker.toDocValue().self_is_null());
+    assert_eq!(Marker::fromDocValue(&DocValue::Null).unwrap(),Marker);
+    let wrapped = Wrapper{inner:Id(3)}.toDocValue();
+    assert_eq!(Wrapper::<Id>::fromDocValue(&wrapped).unwrap(),Wrapper{inner:Id(3)});
+}
+
+#[test]
+fn derived_types_are_try_to_doc_value(){
+    let mut doc = DocValue::object();
+    doc.try_insert("person",person()).unwrap();
+    doc.try_insert("points",vec![Point(1.0,2.0)]).unwrap();
+    assert!(doc.key_is_object("person"));
+    assert_eq!(doc.as_object().unwrap().get("points").unwrap().as_vec().unwrap().len(),1);
+}
+
+#[test]
+fn enums(){
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(2.0),
+        Shape::Line(Point(0.0,0.0),Point(1.0,1.0)),
+        Shape::Rect{width:3,height:4},
+    ];
+    for shape in shapes{
+        let doc = shape.toDocValue();
+        assert_eq!(Shape::fromDocValue(&doc).unwrap(),shape);
+    }
+    assert_eq!(Shape::Empty.toDocValue().as_string().unwrap(),"Empty");
+    assert!(Shape::Circle(2.0).toDocValue().key_is_float("circle"));
+    let rect = Shape::Rect{width:3,height:4}.toDocValue();
+    assert!(rect.as_object().unwrap().get("Rect").unwrap().key_is_num("h"));
+    let error = Shape::fromDocValue(&DocValue::str("Square")).unwrap_err();
+    assert_eq!(error.expected(),"variant of Shape");
+}
+
+#[test]
+fn fields_named_like_generated_locals(){
+    let locals = Locals{map:"m".to_string(),inner:1,variant:true,value:2,other:3,pool:4,meta:Meta{version:5}};
+    let doc = locals.toDocValue();
+    assert_eq!(doc.as_object().unwrap().len(),7);
+    assert_eq!(doc.get("map").unwrap().as_string().unwrap(),"m");
+    assert!(doc.key_is_num("version"));
+    assert_eq!(Locals::fromDocValue(&doc).unwrap(),locals);
+
+    let named = Scope::Named{map:"m".to_string(),inner:1,variant:false,value:2,other:3};
+    let doc = named.toDocValue();
+    let fields = doc.get("Named").unwrap();
+    assert_eq!(fields.as_object().unwrap().len(),5);
+    assert_eq!(Scope::fromDocValue(&doc).unwrap(),named);
+    let tuple = Scope::Tuple(1,"t".to_string());
+    assert_eq!(Scope::fromDocValue(&tuple.toDocValue()).unwrap(),tuple);
+}
+
+#[test]
+fn error_paths(){
+    let mut sports = DocValue::object();
+    sports.insert("game",5);
+    sports.insert("match",4);
+    let mut doc = person().toDocValue();
+    doc.insert("sports",sports);
+    let error:FromDocError = Person::fromDocValue(&doc).unwrap_err();
+    assert_eq!(error.path(),"sports.game");
+    assert_eq!(error.expected(),"string");
+    assert_eq!(error.found(),"num");
+    assert_eq!(error.to_string(),"expected string found num (path: sports.game)");
+
+    let mut line = DocValue::object();
+    let mut points = DocValue::vec();
+    points.push(Point(0.0,0.0));
+    points.push("x");
+    line.insert("Line",points);
+    let error = Shape::fromDocValue(&line).unwrap_err();
+    assert_eq!(error.path(),"Line[1]");
+}
+
+#[test]
+fn builds_without_the_prelude(){
+    let plain = no_prelude::Plain{
+        name:"akku".to_string(),
+        nick:None,
+        pair:no_prelude::Pair(1,true),
+        kind:no_prelude::Kind::Named{x:3},
+        extra:no_prelude::Extra{version:2},
+    };
+    let doc = plain.toDocValue();
+    assert_eq!(no_prelude::Plain::fromDocValue(&doc).unwrap(),plain);
+    for kind in [no_prelude::Kind::Empty,no_prelude::Kind::One(1),no_prelude::Kind::Two(1,2)]{
+        assert_eq!(no_prelude::Kind::fromDocValue(&kind.toDocValue()).unwrap(),kind);
+    }
+    for kind in [shadowed::Kind::Empty,shadowed::Kind::Two(1,2),shadowed::Kind::Named{x:1,y:2}]{
+        assert_eq!(shadowed::Kind::fromDocValue(&kind.toDocValue()).unwrap(),kind);
+    }
+    assert!(shadowed::Kind::fromDocValue(&DocValue::str("Three")).is_err());
+}
@@ -0,0 +1,9 @@
+use gzbbinarydoc::ToDocValue;
+
+#[derive(ToDocValue)]
+#[docvalue(rename = "person")]
+struct Person{
+    name:String,
+}
+
+fn main(){}
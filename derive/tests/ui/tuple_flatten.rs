@@ -0,0 +1,11 @@
+use gzbbinarydoc::FromDocValue;
+
+#[derive(FromDocValue)]
+struct Meta{
+    version:i64,
+}
+
+#[derive(FromDocValue)]
+struct Wrapper(#[docvalue(flatten)] Meta,i64);
+
+fn main(){}
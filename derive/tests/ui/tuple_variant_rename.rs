@@ -0,0 +1,8 @@
+use gzbbinarydoc::ToDocValue;
+
+#[derive(ToDocValue)]
+enum Shape{
+    Line(#[docvalue(rename = "from")] i64,i64),
+}
+
+fn main(){}
@@ -10,7 +10,7 @@ fn main(){
     let mut person = DocValue::object();
 
     person.insert("name","akku");
-    person.insert("bin",vec![1,2,3]);
+    person.insert("bin",vec![1u8,2,3]);
     person.insert("age","five");
     person.insert("score",12.65);
     person.insert(&"king",true);
@@ -0,0 +1,115 @@
+use crate::DecodeError;
+use crate::header::{Header,FLAG_CHECKSUM,HEADER_LEN};
+use std::io::{self,Write};
+
+///length of the checksum trailer, a big endien u32
+pub const CHECKSUM_LEN:usize = 4;
+
+///crc32c (castagnoli) lookup table, reflected polynomial 0x82f63b78
+const TABLE:[u32;256] = build_table();
+
+const fn build_table()->[u32;256]{
+    let mut table = [0u32;256];
+    let mut index = 0;
+    while index < 256{
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8{
+            if crc & 1 == 1{
+                crc = (crc >> 1) ^ 0x82f6_3b78;
+            } else {
+                crc >>= 1;
+            }
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+}
+
+///
+/// running crc32c of the bytes fed to update, the same checksum iscsi
+/// and ext4 use.
+///
+#[derive(Clone,Copy,Debug)]
+pub struct Crc32c{
+    state:u32,
+}
+
+impl Crc32c{
+    pub fn new()->Crc32c{
+        Crc32c{state:!0}
+    }
+    pub fn update(&mut self,data:&[u8]){
+        let mut crc = self.state;
+        for byte in data{
+            crc = TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8);
+        }
+        self.state = crc;
+    }
+    pub fn finish(&self)->u32{
+        !self.state
+    }
+}
+
+pub fn crc32c(data:&[u8])->u32{
+    let mut crc = Crc32c::new();
+    crc.update(data);
+    crc.finish()
+}
+
+///passes writes through to out and feeds them to the checksum
+pub struct ChecksumWriter<'w,W:Write>{
+    pub out:&'w mut W,
+    pub crc:Crc32c,
+}
+
+impl<'w,W:Write> Write for ChecksumWriter<'w,W>{
+    fn write(&mut self,buf:&[u8])->io::Result<usize>{
+        let size = self.out.write(buf)?;
+        self.crc.update(&buf[..size]);
+        Ok(size)
+    }
+    fn flush(&mut self)->io::Result<()>{
+        self.out.flush()
+    }
+}
+
+///
+/// checks the trailer of a document whose header sets the checksum flag,
+/// returns the length of data without the trailer.
+///
+pub fn check(data:&[u8])->Result<usize,DecodeError>{
+    if data.len() < HEADER_LEN + CHECKSUM_LEN{
+        return Err(DecodeError::Truncated{offset:data.len(),path:String::new()});
+    }
+    let body_len = data.len() - CHECKSUM_LEN;
+    let mut stored = [0u8;CHECKSUM_LEN];
+    stored.copy_from_slice(&data[body_len..]);
+    let expected = u32::from_be_bytes(stored);
+    let found = crc32c(&data[..body_len]);
+    if expected != found{
+        return Err(DecodeError::ChecksumMismatch{expected,found,offset:body_len});
+    }
+    Ok(body_len)
+}
+
+///
+/// verifies the checksum trailer of a encoded document without decoding it.
+/// returns Ok(true) if the checksum matches and Ok(false) if the document
+/// was written without one, the whole buffer is taken as one document.
+///
+/// let options = EncodeOptions{checksum:true,..EncodeOptions::default()};
+/// let bin = person.write_with(options);
+/// assert!(gzbbinarydoc::verify(&bin).unwrap());
+///
+pub fn verify(data:&[u8])->Result<bool,DecodeError>{
+    match Header::parse(data)?{
+        Some(header) if header.flags & FLAG_CHECKSUM != 0=>{
+            check(data)?;
+            Ok(true)
+        },
+        _=>{Ok(false)}
+    }
+}
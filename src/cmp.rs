@@ -0,0 +1,95 @@
+use crate::{DocValue,Map};
+use crate::writer::canonical_float;
+use std::cmp::Ordering;
+use std::hash::{Hash,Hasher};
+
+// equality, hashing and a total order of DocValue.
+//
+// values of different variants are never equal, Num(1) != Float(1.0), and
+// order by variant: null < bool < num < float < string < binary < vec < object.
+// floats compare like canonical encoding writes them, -0.0 == 0.0 and every
+// nan is equal to every other nan and greater than all other floats, so two
+// documents are equal exactly when their canonical encodings are equal.
+// objects compare their entries sorted by key bytes, insertion order never matters.
+
+fn rank(value:&DocValue)->u8{
+    match value{
+        DocValue::Null=>{0},
+        DocValue::Bool(_)=>{1},
+        DocValue::Num(_)=>{2},
+        DocValue::Float(_)=>{3},
+        DocValue::String(_)=>{4},
+        DocValue::Binary(_)=>{5},
+        DocValue::Vec(_)=>{6},
+        DocValue::Object(_)=>{7},
+    }
+}
+
+fn sorted(map:&Map)->Vec<(&String,&DocValue)>{
+    let mut entries:Vec<(&String,&DocValue)> = map.iter().collect();
+    entries.sort_unstable_by(|a,b|a.0.cmp(b.0));
+    entries
+}
+
+impl PartialEq for DocValue{
+    fn eq(&self,other:&DocValue)->bool{
+        match (self,other){
+            (DocValue::Object(a),DocValue::Object(b))=>{
+                a.len() == b.len() && a.iter().all(|(k,v)|b.get(k) == Some(v))
+            },
+            (DocValue::Vec(a),DocValue::Vec(b))=>{a == b},
+            (DocValue::Num(a),DocValue::Num(b))=>{a == b},
+            (DocValue::String(a),DocValue::String(b))=>{a == b},
+            (DocValue::Float(a),DocValue::Float(b))=>{canonical_float(*a).to_bits() == canonical_float(*b).to_bits()},
+            (DocValue::Binary(a),DocValue::Binary(b))=>{a == b},
+            (DocValue::Null,DocValue::Null)=>{true},
+            (DocValue::Bool(a),DocValue::Bool(b))=>{a == b},
+            _=>{false}
+        }
+    }
+}
+
+impl Eq for DocValue{}
+
+impl Ord for DocValue{
+    fn cmp(&self,other:&DocValue)->Ordering{
+        match (self,other){
+            (DocValue::Object(a),DocValue::Object(b))=>{sorted(a).cmp(&sorted(b))},
+            (DocValue::Vec(a),DocValue::Vec(b))=>{a.cmp(b)},
+            (DocValue::Num(a),DocValue::Num(b))=>{a.cmp(b)},
+            (DocValue::String(a),DocValue::String(b))=>{a.cmp(b)},
+            (DocValue::Float(a),DocValue::Float(b))=>{canonical_float(*a).total_cmp(&canonical_float(*b))},
+            (DocValue::Binary(a),DocValue::Binary(b))=>{a.cmp(b)},
+            (DocValue::Bool(a),DocValue::Bool(b))=>{a.cmp(b)},
+            _=>{rank(self).cmp(&rank(other))}
+        }
+    }
+}
+
+impl PartialOrd for DocValue{
+    fn partial_cmp(&self,other:&DocValue)->Option<Ordering>{
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for DocValue{
+    fn hash<H:Hasher>(&self,state:&mut H){
+        rank(self).hash(state);
+        match self{
+            DocValue::Object(map)=>{
+                map.len().hash(state);
+                for (key,value) in sorted(map){
+                    key.hash(state);
+                    value.hash(state);
+                }
+            },
+            DocValue::Vec(pool)=>{pool.hash(state);},
+            DocValue::Num(v)=>{v.hash(state);},
+            DocValue::String(v)=>{v.hash(state);},
+            DocValue::Float(v)=>{canonical_float(*v).to_bits().hash(state);},
+            DocValue::Binary(v)=>{v.hash(state);},
+            DocValue::Null=>{},
+            DocValue::Bool(v)=>{v.hash(state);},
+        }
+    }
+}
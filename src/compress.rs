@@ -0,0 +1,95 @@
+use crate::{DocValue,DecodeError,DecodeOptions};
+use crate::error::Limit;
+use crate::header::{Header,FLAG_CHECKSUM,FLAG_COMPRESSED,HEADER_LEN};
+use crate::checksum;
+use std::io::{self,Write};
+
+///
+/// documents whose root data_line is smaller than this many bytes are
+/// left uncompressed by DocValue::write_compressed
+///
+pub const COMPRESSION_THRESHOLD:usize = 512;
+
+///length of the uncompressed and compressed length in front of the block
+const BLOCK_HEAD_LEN:usize = 16;
+
+///worst case expansion of a lz4 block
+const MAX_RATIO:u64 = 255;
+
+///
+/// writes the encoded root data_line as a lz4 block, data is the encoded
+/// data_line without header.
+///
+pub fn write_block<W:Write>(out:&mut W,data:&[u8])->io::Result<()>{
+    let block = lz4_flex::block::compress(data);
+    out.write_all(&(data.len() as u64).to_be_bytes())?;
+    out.write_all(&(block.len() as u64).to_be_bytes())?;
+    out.write_all(&block)
+}
+
+///
+/// decompresses a document whose header sets the compressed flag into a
+/// plain document with a header, Ok(None) if data is not compressed.
+/// offsets of errors found later point into the decompressed document.
+///
+pub fn decompress(data:&[u8],options:DecodeOptions)->Result<Option<Vec<u8>>,DecodeError>{
+
+    let header = match Header::parse(data)?{
+        Some(v) if v.flags & FLAG_COMPRESSED != 0=>{v},
+        _=>{return Ok(None);}
+    };
+
+    let mut end = data.len();
+    if header.flags & FLAG_CHECKSUM != 0{
+        end = checksum::check(data)?;
+    }
+
+    let block_start = HEADER_LEN + BLOCK_HEAD_LEN;
+    if end < block_start{
+        return Err(DecodeError::Truncated{offset:end,path:String::new()});
+    }
+    let mut len_bytes = [0u8;8];
+    len_bytes.copy_from_slice(&data[HEADER_LEN..HEADER_LEN + 8]);
+    let data_len = u64::from_be_bytes(len_bytes);
+    len_bytes.copy_from_slice(&data[HEADER_LEN + 8..block_start]);
+    let block_len = u64::from_be_bytes(len_bytes);
+
+    if block_len > (end - block_start) as u64{
+        return Err(DecodeError::Truncated{offset:end,path:String::new()});
+    }
+    let block_end = block_start + block_len as usize;
+    if block_end != end{
+        return Err(DecodeError::TrailingBytes{offset:block_end,path:String::new()});
+    }
+    if data_len > options.max_total_bytes as u64{
+        return Err(DecodeError::LimitExceeded{limit:Limit::TotalBytes,offset:HEADER_LEN,path:String::new()});
+    }
+    //a lz4 block expands at most 255 times, a larger stated length is a
+    //corrupt or hostile header and is rejected before allocating it
+    if data_len > block_len.saturating_mul(MAX_RATIO).saturating_add(BLOCK_HEAD_LEN as u64){
+        return Err(DecodeError::InvalidCompressed{offset:HEADER_LEN});
+    }
+    let data_len = data_len as usize;
+
+    let flags = header.flags & !(FLAG_COMPRESSED | FLAG_CHECKSUM);
+    let mut build = Vec::with_capacity(HEADER_LEN + data_len);
+    build.extend_from_slice(&Header::new(header.version,flags).to_bytes());
+    build.resize(HEADER_LEN + data_len,0);
+    match lz4_flex::block::decompress_into(&data[block_start..block_end],&mut build[HEADER_LEN..]){
+        Ok(size) if size == data_len=>{},
+        _=>{return Err(DecodeError::InvalidCompressed{offset:block_start});}
+    }
+
+    Ok(Some(build))
+
+}
+
+impl DocValue{
+    ///
+    /// writes the document as a lz4 block if its encoding is at least
+    /// COMPRESSION_THRESHOLD bytes, read decompresses it transparently.
+    ///
+    pub fn write_compressed(&self)->Vec<u8>{
+        self.write_with(crate::EncodeOptions{compression:Some(COMPRESSION_THRESHOLD),..crate::EncodeOptions::default()})
+    }
+}
@@ -0,0 +1,402 @@
+use crate::{DocRef,DocValue,DecodeError,DecodeOptions,Map,SerdeError};
+use crate::reader::{Reader,SubReader,Entries,PathSegment,read_head,read_key,read_len,process_data};
+use std::fmt;
+use serde::de::{self,Deserialize,DeserializeOwned,DeserializeSeed,Visitor,value::BorrowedStrDeserializer};
+
+///
+/// decodes a Deserialize type straight from a encoded document without
+/// building a DocValue, strings and binaries can be borrowed from data.
+/// reads every format read does, compressed documents return
+/// DecodeError::Compressed since nothing can borrow from them, use
+/// from_bytes_owned for those.
+///
+/// let person:Person = gzbbinarydoc::from_bytes(&bin)?;
+///
+pub fn from_bytes<'de,T:Deserialize<'de>>(data:&'de [u8])->Result<T,SerdeError>{
+    from_bytes_with(data,DecodeOptions::default())
+}
+
+pub fn from_bytes_with<'de,T:Deserialize<'de>>(data:&'de [u8],options:DecodeOptions)->Result<T,SerdeError>{
+    let mut global = Reader::new(data,options);
+    let mut frame = global.root()?;
+    let value = T::deserialize(Deserializer{global:&mut global,frame:&mut frame})?;
+    frame.finish(&mut global)?;
+    Ok(value)
+}
+
+///
+/// decodes a DeserializeOwned type, unlike from_bytes a compressed
+/// document is decompressed into a owned buffer first like read does.
+///
+/// let person:Person = gzbbinarydoc::from_bytes_owned(&bin)?;
+///
+pub fn from_bytes_owned<T:DeserializeOwned>(data:&[u8])->Result<T,SerdeError>{
+    from_bytes_owned_with(data,DecodeOptions::default())
+}
+
+pub fn from_bytes_owned_with<T:DeserializeOwned>(data:&[u8],options:DecodeOptions)->Result<T,SerdeError>{
+    #[cfg(feature = "compression")]
+    if let Some(data) = crate::compress::decompress(data,options)?{
+        return from_bytes_with(&data,options);
+    }
+    from_bytes_with(data,options)
+}
+
+///deserializes the data_line at the cursor of frame
+struct Deserializer<'de,'g>{
+    global:&'g mut Reader<'de>,
+    frame:&'g mut SubReader,
+}
+
+impl<'de,'g> Deserializer<'de,'g>{
+    fn error(&self,expected:&str,offset:usize)->SerdeError{
+        SerdeError::Message(format!("expected {} at byte {} (path: {})",expected,offset,self.global.path()))
+    }
+}
+
+impl<'de,'g> de::Deserializer<'de> for Deserializer<'de,'g>{
+
+    type Error = SerdeError;
+
+    fn deserialize_any<V:Visitor<'de>>(self,visitor:V)->Result<V::Value,SerdeError>{
+        let (data_type,type_offset,mut data) = read_head(self.global,self.frame)?;
+        let value = match data_type{
+            0=>{
+                let mut access = EntryAccess::new(self.global,&mut data)?;
+                let value = visitor.visit_map(&mut access)?;
+                access.end()?;
+                value
+            },
+            1=>{
+                let mut access = EntryAccess::new(self.global,&mut data)?;
+                let value = visitor.visit_seq(&mut access)?;
+                access.end()?;
+                value
+            },
+            _=>{
+                let value:Result<V::Value,SerdeError> = match process_data(data_type,type_offset,self.global,&mut data)?{
+                    DocRef::Binary(v)=>{visitor.visit_borrowed_bytes(v)},
+                    DocRef::String(v)=>{visitor.visit_borrowed_str(v)},
+                    DocRef::Num(v)=>{visitor.visit_i64(v)},
+                    DocRef::Float(v)=>{visitor.visit_f64(v)},
+                    DocRef::Bool(v)=>{visitor.visit_bool(v)},
+                    DocRef::Null=>{visitor.visit_unit()},
+                    DocRef::Object(_) | DocRef::Vec(_)=>{unreachable!("objects and vecs are visited above")},
+                };
+                value?
+            }
+        };
+        data.finish(self.global)?;
+        Ok(value)
+    }
+
+    fn deserialize_option<V:Visitor<'de>>(self,visitor:V)->Result<V::Value,SerdeError>{
+        if self.frame.peek(self.global) != Some(7){
+            return visitor.visit_some(self);
+        }
+        let (data_type,type_offset,mut data) = read_head(self.global,self.frame)?;
+        process_data(data_type,type_offset,self.global,&mut data)?;
+        data.finish(self.global)?;
+        visitor.visit_none()
+    }
+
+    ///unit variants are strings, the others a object with the variant name as its only key
+    fn deserialize_enum<V:Visitor<'de>>(self,_name:&'static str,_variants:&'static [&'static str],visitor:V)->Result<V::Value,SerdeError>{
+        let (data_type,type_offset,mut data) = read_head(self.global,self.frame)?;
+        let value = match data_type{
+            3=>{
+                match process_data(data_type,type_offset,self.global,&mut data)?{
+                    DocRef::String(v)=>{visitor.visit_enum(BorrowedStrDeserializer::<SerdeError>::new(v))?},
+                    _=>{return Err(self.error("a string",type_offset));}
+                }
+            },
+            0=>{
+                let mut access = EntryAccess::new(self.global,&mut data)?;
+                if !access.next()?{
+                    return Err(access.error("a object with one key",type_offset));
+                }
+                let value = visitor.visit_enum(&mut access)?;
+                if access.next()?{
+                    return Err(access.error("a object with one key",type_offset));
+                }
+                access.end()?;
+                value
+            },
+            _=>{return Err(self.error("a string or object holding a enum",type_offset));}
+        };
+        data.finish(self.global)?;
+        Ok(value)
+    }
+
+    fn deserialize_newtype_struct<V:Visitor<'de>>(self,_name:&'static str,visitor:V)->Result<V::Value,SerdeError>{
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn is_human_readable(&self)->bool{
+        false
+    }
+
+    serde::forward_to_deserialize_any!{
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+
+}
+
+///
+/// entries of a object or vec being visited, end checks that all were
+/// read and leaves the object or vec.
+///
+struct EntryAccess<'de,'g>{
+    global:&'g mut Reader<'de>,
+    frame:&'g mut SubReader,
+    entries:Entries,
+    index:usize,
+    done:bool,
+}
+
+impl<'de,'g> EntryAccess<'de,'g>{
+    fn new(global:&'g mut Reader<'de>,frame:&'g mut SubReader)->Result<EntryAccess<'de,'g>,DecodeError>{
+        global.enter(frame.offset())?;
+        let entries = Entries::new(global,frame)?;
+        Ok(EntryAccess{global,frame,entries,index:0,done:false})
+    }
+    ///true if another entry follows, keeps returning false once the entries ended
+    fn next(&mut self)->Result<bool,DecodeError>{
+        if self.done{
+            return Ok(false);
+        }
+        let more = self.entries.next(self.global,self.frame)?;
+        self.done = !more;
+        Ok(more)
+    }
+    ///errors if the visitor left entries unread
+    fn end(mut self)->Result<(),DecodeError>{
+        if self.next()?{
+            return Err(DecodeError::TrailingBytes{offset:self.frame.offset(),path:self.global.path()});
+        }
+        self.global.leave();
+        Ok(())
+    }
+    fn error(&self,expected:&str,offset:usize)->SerdeError{
+        SerdeError::Message(format!("expected {} at byte {} (path: {})",expected,offset,self.global.path()))
+    }
+    ///deserializes the value of the current entry and pops its path segment
+    fn value<T,F>(&mut self,f:F)->Result<T,SerdeError>
+    where F:for<'x> FnOnce(Deserializer<'de,'x>)->Result<T,SerdeError>{
+        let value = if self.global.version() == 1{
+            let entry_len = read_len(self.global,self.frame)?;
+            let mut entry = self.frame.sub(self.global,entry_len)?;
+            let value = f(Deserializer{global:&mut *self.global,frame:&mut entry})?;
+            entry.finish(self.global)?;
+            value
+        } else {
+            f(Deserializer{global:&mut *self.global,frame:&mut *self.frame})?
+        };
+        self.global.pop_path();
+        Ok(value)
+    }
+}
+
+impl<'de,'g,'a> de::MapAccess<'de> for &'a mut EntryAccess<'de,'g>{
+    type Error = SerdeError;
+    fn next_key_seed<K:DeserializeSeed<'de>>(&mut self,seed:K)->Result<Option<K::Value>,SerdeError>{
+        if !self.next()?{
+            return Ok(None);
+        }
+        let key = read_key(self.global,self.frame)?;
+        self.global.push_path(PathSegment::Key(key));
+        seed.deserialize(KeyDeserializer{key}).map(Some)
+    }
+    fn next_value_seed<V:DeserializeSeed<'de>>(&mut self,seed:V)->Result<V::Value,SerdeError>{
+        self.value(|d|seed.deserialize(d))
+    }
+    fn size_hint(&self)->Option<usize>{
+        self.entries.count()
+    }
+}
+
+impl<'de,'g,'a> de::SeqAccess<'de> for &'a mut EntryAccess<'de,'g>{
+    type Error = SerdeError;
+    fn next_element_seed<T:DeserializeSeed<'de>>(&mut self,seed:T)->Result<Option<T::Value>,SerdeError>{
+        if !self.next()?{
+            return Ok(None);
+        }
+        self.global.push_path(PathSegment::Index(self.index));
+        self.index += 1;
+        self.value(|d|seed.deserialize(d)).map(Some)
+    }
+    fn size_hint(&self)->Option<usize>{
+        self.entries.count()
+    }
+}
+
+impl<'de,'g,'a> de::EnumAccess<'de> for &'a mut EntryAccess<'de,'g>{
+    type Error = SerdeError;
+    type Variant = Self;
+    fn variant_seed<V:DeserializeSeed<'de>>(self,seed:V)->Result<(V::Value,Self),SerdeError>{
+        let key = read_key(self.global,self.frame)?;
+        self.global.push_path(PathSegment::Key(key));
+        let variant = seed.deserialize(BorrowedStrDeserializer::<SerdeError>::new(key))?;
+        Ok((variant,self))
+    }
+}
+
+impl<'de,'g,'a> de::VariantAccess<'de> for &'a mut EntryAccess<'de,'g>{
+    type Error = SerdeError;
+    fn unit_variant(self)->Result<(),SerdeError>{
+        self.value(|d|<()>::deserialize(d))
+    }
+    fn newtype_variant_seed<T:DeserializeSeed<'de>>(self,seed:T)->Result<T::Value,SerdeError>{
+        self.value(|d|seed.deserialize(d))
+    }
+    fn tuple_variant<V:Visitor<'de>>(self,_len:usize,visitor:V)->Result<V::Value,SerdeError>{
+        self.value(|d|de::Deserializer::deserialize_seq(d,visitor))
+    }
+    fn struct_variant<V:Visitor<'de>>(self,_fields:&'static [&'static str],visitor:V)->Result<V::Value,SerdeError>{
+        self.value(|d|de::Deserializer::deserialize_map(d,visitor))
+    }
+}
+
+///
+/// object keys, integer map keys are parsed back from the text the
+/// serializer writes for them.
+///
+struct KeyDeserializer<'de>{
+    key:&'de str,
+}
+
+macro_rules! parse_key{
+    ($($method:ident $visit:ident $ty:ty),*)=>{
+        $(
+            fn $method<V:Visitor<'de>>(self,visitor:V)->Result<V::Value,SerdeError>{
+                match self.key.parse::<$ty>(){
+                    Ok(v)=>{visitor.$visit(v)},
+                    Err(_)=>{Err(SerdeError::Message(format!("key {:?} is not a {}",self.key,stringify!($ty))))}
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'de>{
+
+    type Error = SerdeError;
+
+    fn deserialize_any<V:Visitor<'de>>(self,visitor:V)->Result<V::Value,SerdeError>{
+        visitor.visit_borrowed_str(self.key)
+    }
+    fn deserialize_enum<V:Visitor<'de>>(self,_name:&'static str,_variants:&'static [&'static str],visitor:V)->Result<V::Value,SerdeError>{
+        visitor.visit_enum(BorrowedStrDeserializer::<SerdeError>::new(self.key))
+    }
+    fn deserialize_newtype_struct<V:Visitor<'de>>(self,_name:&'static str,visitor:V)->Result<V::Value,SerdeError>{
+        visitor.visit_newtype_struct(self)
+    }
+
+    parse_key!{
+        deserialize_i8 visit_i8 i8,
+        deserialize_i16 visit_i16 i16,
+        deserialize_i32 visit_i32 i32,
+        deserialize_i64 visit_i64 i64,
+        deserialize_i128 visit_i128 i128,
+        deserialize_u8 visit_u8 u8,
+        deserialize_u16 visit_u16 u16,
+        deserialize_u32 visit_u32 u32,
+        deserialize_u64 visit_u64 u64,
+        deserialize_u128 visit_u128 u128
+    }
+
+    serde::forward_to_deserialize_any!{
+        bool f32 f64 char str string bytes byte_buf option unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+
+}
+
+///
+/// builds a DocValue from any serde format, integers become Num and fail
+/// if they do not fit in a i64, floats become Float, bytes Binary and
+/// unit and None Null. formats without a bytes type like json read
+/// binary back as a vec of nums.
+///
+impl<'de> Deserialize<'de> for DocValue{
+    fn deserialize<D:de::Deserializer<'de>>(deserializer:D)->Result<DocValue,D::Error>{
+        deserializer.deserialize_any(DocValueVisitor)
+    }
+}
+
+struct DocValueVisitor;
+
+impl<'de> Visitor<'de> for DocValueVisitor{
+
+    type Value = DocValue;
+
+    fn expecting(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        write!(f,"a document value")
+    }
+
+    fn visit_bool<E:de::Error>(self,v:bool)->Result<DocValue,E>{
+        Ok(DocValue::Bool(v))
+    }
+    fn visit_i64<E:de::Error>(self,v:i64)->Result<DocValue,E>{
+        Ok(DocValue::Num(v))
+    }
+    fn visit_i128<E:de::Error>(self,v:i128)->Result<DocValue,E>{
+        match i64::try_from(v){
+            Ok(v)=>{Ok(DocValue::Num(v))},
+            Err(_)=>{Err(E::custom(format!("i128 {} does not fit in a i64 num",v)))}
+        }
+    }
+    fn visit_u64<E:de::Error>(self,v:u64)->Result<DocValue,E>{
+        match i64::try_from(v){
+            Ok(v)=>{Ok(DocValue::Num(v))},
+            Err(_)=>{Err(E::custom(format!("u64 {} does not fit in a i64 num",v)))}
+        }
+    }
+    fn visit_u128<E:de::Error>(self,v:u128)->Result<DocValue,E>{
+        match i64::try_from(v){
+            Ok(v)=>{Ok(DocValue::Num(v))},
+            Err(_)=>{Err(E::custom(format!("u128 {} does not fit in a i64 num",v)))}
+        }
+    }
+    fn visit_f64<E:de::Error>(self,v:f64)->Result<DocValue,E>{
+        Ok(DocValue::Float(v))
+    }
+    fn visit_str<E:de::Error>(self,v:&str)->Result<DocValue,E>{
+        Ok(DocValue::String(v.to_string()))
+    }
+    fn visit_string<E:de::Error>(self,v:String)->Result<DocValue,E>{
+        Ok(DocValue::String(v))
+    }
+    fn visit_bytes<E:de::Error>(self,v:&[u8])->Result<DocValue,E>{
+        Ok(DocValue::Binary(v.to_vec()))
+    }
+    fn visit_byte_buf<E:de::Error>(self,v:Vec<u8>)->Result<DocValue,E>{
+        Ok(DocValue::Binary(v))
+    }
+    fn visit_unit<E:de::Error>(self)->Result<DocValue,E>{
+        Ok(DocValue::Null)
+    }
+    fn visit_none<E:de::Error>(self)->Result<DocValue,E>{
+        Ok(DocValue::Null)
+    }
+    fn visit_some<D:de::Deserializer<'de>>(self,deserializer:D)->Result<DocValue,D::Error>{
+        DocValue::deserialize(deserializer)
+    }
+    fn visit_seq<A:de::SeqAccess<'de>>(self,mut seq:A)->Result<DocValue,A::Error>{
+        let mut build = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(item) = seq.next_element()?{
+            build.push(item);
+        }
+        Ok(DocValue::Vec(build))
+    }
+    fn visit_map<A:de::MapAccess<'de>>(self,mut map:A)->Result<DocValue,A::Error>{
+        let mut build = Map::with_capacity(map.size_hint().unwrap_or(0).min(4096));
+        while let Some((key,value)) = map.next_entry::<String,DocValue>()?{
+            build.insert(key,value);
+        }
+        Ok(DocValue::Object(build))
+    }
+
+}
@@ -1,26 +1,33 @@
-use std::{collections::HashMap};
-use std::collections::hash_map::Iter as HashMapIter;
-use std::collections::hash_map::IterMut as HashMapIterMut;
+use std::{collections::{BTreeMap,HashMap}};
+use crate::{Map,MapIter,MapIterMut};
 
 #[derive(Clone,Debug)]
 pub enum DocValue{
-    Object(HashMap<String,DocValue>),Vec(Vec<DocValue>),Num(i64),String(String),Float(f64),Binary(Vec<u8>),Null,Bool(bool)
+    Object(Map<String,DocValue>),Vec(Vec<DocValue>),Num(i64),String(String),Float(f64),Binary(Vec<u8>),Null,Bool(bool)
 }
 
 #[allow(non_snake_case)]
 pub trait ToDocValue{
     fn toDocValue(&self)->DocValue;
+    ///
+    /// builds a DocValue from a slice of Self, a vec by default. u8 builds
+    /// binary instead so Vec<u8> stays binary while Vec<T> is a vec for every other T.
+    ///
+    #[doc(hidden)]
+    fn toDocVec(pool:&[Self])->DocValue where Self:Sized{
+        DocValue::Vec(pool.iter().map(|v|v.toDocValue()).collect())
+    }
 }
 
 impl ToDocValue for DocValue{
     fn toDocValue(&self)->DocValue {
-        return self.clone()
+        self.clone()
     }
 }
 
 impl ToDocValue for bool{
     fn toDocValue(&self)->DocValue {
-        DocValue::Bool(self.clone())
+        DocValue::Bool(*self)
     }
 }
 
@@ -30,7 +37,13 @@ impl ToDocValue for String{
     }
 }
 
-impl ToDocValue for &str{
+impl ToDocValue for str{
+    fn toDocValue(&self)->DocValue {
+        DocValue::String(self.to_string())
+    }
+}
+
+impl ToDocValue for char{
     fn toDocValue(&self)->DocValue {
         DocValue::String(self.to_string())
     }
@@ -38,25 +51,117 @@ impl ToDocValue for &str{
 
 impl ToDocValue for i64{
     fn toDocValue(&self)->DocValue {
-        DocValue::Num(self.clone())
+        DocValue::Num(*self)
     }
 }
 
-impl ToDocValue for f64{
+///integers that always fit in a num
+macro_rules! to_num{
+    ($($t:ty),*)=>{
+        $(
+            impl ToDocValue for $t{
+                fn toDocValue(&self)->DocValue {
+                    DocValue::Num(i64::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+to_num!(i8,i16,i32,u16,u32);
+
+///
+/// fallible conversion used by try_insert and try_push. every ToDocValue
+/// type of this crate and every type deriving ToDocValue converts without
+/// error, integers that can be wider than a num (i128, isize, u64, u128,
+/// usize) only implement this trait and return a error for values outside
+/// the i64 range instead of panicking. options, references, slices,
+/// arrays, vecs, maps and tuples of any of them fail on their first entry
+/// that does not fit.
+///
+#[allow(non_snake_case)]
+pub trait TryToDocValue{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>;
+    ///fallible toDocVec, u8 builds binary
+    #[doc(hidden)]
+    fn tryToDocVec(pool:&[Self])->Result<DocValue,ToDocError> where Self:Sized{
+        let build = pool.iter().map(|v|v.tryToDocValue()).collect::<Result<Vec<DocValue>,ToDocError>>()?;
+        Ok(DocValue::Vec(build))
+    }
+}
+
+///integers wider than a num
+macro_rules! try_to_num{
+    ($($t:ty),*)=>{
+        $(
+            impl TryToDocValue for $t{
+                fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+                    match i64::try_from(*self){
+                        Ok(v)=>{Ok(DocValue::Num(v))},
+                        Err(_)=>{Err(ToDocError::new(stringify!($t),&self.to_string()))}
+                    }
+                }
+            }
+            impl TryFrom<$t> for DocValue{
+                type Error = ToDocError;
+                fn try_from(value:$t)->Result<DocValue,ToDocError>{
+                    value.tryToDocValue()
+                }
+            }
+        )*
+    };
+}
+
+try_to_num!(i128,isize,u64,u128,usize);
+
+///
+/// error of TryToDocValue, a integer outside the i64 range of a num
+///
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct ToDocError{
+    type_name:&'static str,
+    value:String,
+}
+
+impl ToDocError{
+    pub fn new(type_name:&'static str,value:&str)->ToDocError{
+        ToDocError{type_name,value:value.to_string()}
+    }
+    ///rust type of the value e.g. "u64"
+    pub fn type_name(&self)->&'static str{
+        self.type_name
+    }
+    pub fn value(&self)->&str{
+        &self.value
+    }
+}
+
+impl std::fmt::Display for ToDocError{
+    fn fmt(&self,f:&mut std::fmt::Formatter<'_>)->std::fmt::Result{
+        write!(f,"{} {} does not fit in a num",self.type_name,self.value)
+    }
+}
+
+impl std::error::Error for ToDocError{}
+
+impl ToDocValue for u8{
     fn toDocValue(&self)->DocValue {
-        DocValue::Float(self.clone())
+        DocValue::Num(i64::from(*self))
+    }
+    fn toDocVec(pool:&[Self])->DocValue {
+        DocValue::Binary(pool.to_vec())
     }
 }
 
-impl ToDocValue for Vec<DocValue>{
+impl ToDocValue for f64{
     fn toDocValue(&self)->DocValue {
-        DocValue::Vec(self.clone())
+        DocValue::Float(*self)
     }
 }
 
-impl ToDocValue for Vec<u8>{
+impl ToDocValue for f32{
     fn toDocValue(&self)->DocValue {
-        DocValue::Binary(self.clone())
+        DocValue::Float(f64::from(*self))
     }
 }
 
@@ -66,55 +171,241 @@ impl ToDocValue for (){
     }
 }
 
-impl ToDocValue for HashMap<String,DocValue>{
+///None is written as null
+impl<T:ToDocValue> ToDocValue for Option<T>{
     fn toDocValue(&self)->DocValue {
-        DocValue::Object(self.clone())
+        match self{
+            Some(v)=>{v.toDocValue()},
+            None=>{DocValue::Null}
+        }
+    }
+}
+
+impl<T:ToDocValue + ?Sized> ToDocValue for &T{
+    fn toDocValue(&self)->DocValue {
+        (**self).toDocValue()
+    }
+}
+
+impl<T:ToDocValue + ?Sized> ToDocValue for Box<T>{
+    fn toDocValue(&self)->DocValue {
+        (**self).toDocValue()
+    }
+}
+
+///a vec, or binary for [u8]
+impl<T:ToDocValue> ToDocValue for [T]{
+    fn toDocValue(&self)->DocValue {
+        T::toDocVec(self)
+    }
+}
+
+impl<T:ToDocValue,const N:usize> ToDocValue for [T;N]{
+    fn toDocValue(&self)->DocValue {
+        T::toDocVec(self)
+    }
+}
+
+impl<T:ToDocValue> ToDocValue for Vec<T>{
+    fn toDocValue(&self)->DocValue {
+        T::toDocVec(self)
+    }
+}
+
+impl<T:ToDocValue,S> ToDocValue for HashMap<String,T,S>{
+    fn toDocValue(&self)->DocValue {
+        DocValue::Object(self.iter().map(|(k,v)|(k.clone(),v.toDocValue())).collect())
+    }
+}
+
+impl<T:ToDocValue> ToDocValue for BTreeMap<String,T>{
+    fn toDocValue(&self)->DocValue {
+        DocValue::Object(self.iter().map(|(k,v)|(k.clone(),v.toDocValue())).collect())
+    }
+}
+
+impl<T:ToDocValue> ToDocValue for Map<String,T>{
+    fn toDocValue(&self)->DocValue {
+        DocValue::Object(self.iter().map(|(k,v)|(k.clone(),v.toDocValue())).collect())
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl<T:ToDocValue,S> ToDocValue for indexmap::IndexMap<String,T,S>{
+    fn toDocValue(&self)->DocValue {
+        DocValue::Object(self.iter().map(|(k,v)|(k.clone(),v.toDocValue())).collect())
+    }
+}
+
+///tuples are written as a vec with one entry per field
+macro_rules! to_tuple{
+    ($($t:ident $index:tt),*)=>{
+        impl<$($t:ToDocValue),*> ToDocValue for ($($t,)*){
+            fn toDocValue(&self)->DocValue {
+                DocValue::Vec(vec![$(self.$index.toDocValue()),*])
+            }
+        }
+        impl<$($t:TryToDocValue),*> TryToDocValue for ($($t,)*){
+            fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+                Ok(DocValue::Vec(vec![$(self.$index.tryToDocValue()?),*]))
+            }
+        }
+    };
+}
+
+to_tuple!(A 0);
+to_tuple!(A 0,B 1);
+to_tuple!(A 0,B 1,C 2);
+to_tuple!(A 0,B 1,C 2,D 3);
+to_tuple!(A 0,B 1,C 2,D 3,E 4);
+to_tuple!(A 0,B 1,C 2,D 3,E 4,F 5);
+to_tuple!(A 0,B 1,C 2,D 3,E 4,F 5,G 6);
+
+///types whose ToDocValue can not fail
+macro_rules! try_to_doc{
+    ($($t:ty),*)=>{
+        $(
+            impl TryToDocValue for $t{
+                fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+                    Ok(self.toDocValue())
+                }
+            }
+        )*
+    };
+}
+
+try_to_doc!(DocValue,bool,String,str,char,i8,i16,i32,i64,u16,u32,f32,f64,());
+
+impl TryToDocValue for u8{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        Ok(self.toDocValue())
+    }
+    fn tryToDocVec(pool:&[Self])->Result<DocValue,ToDocError>{
+        Ok(u8::toDocVec(pool))
+    }
+}
+
+impl<T:TryToDocValue> TryToDocValue for Option<T>{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        match self{
+            Some(v)=>{v.tryToDocValue()},
+            None=>{Ok(DocValue::Null)}
+        }
+    }
+}
+
+impl<T:TryToDocValue + ?Sized> TryToDocValue for &T{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        (**self).tryToDocValue()
+    }
+}
+
+impl<T:TryToDocValue + ?Sized> TryToDocValue for Box<T>{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        (**self).tryToDocValue()
+    }
+}
+
+impl<T:TryToDocValue> TryToDocValue for [T]{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        T::tryToDocVec(self)
+    }
+}
+
+impl<T:TryToDocValue,const N:usize> TryToDocValue for [T;N]{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        T::tryToDocVec(self)
+    }
+}
+
+impl<T:TryToDocValue> TryToDocValue for Vec<T>{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        T::tryToDocVec(self)
+    }
+}
+
+///object of a map, fails on the first value that does not fit
+fn try_object<'a,T:TryToDocValue + 'a>(entries:impl Iterator<Item = (&'a String,&'a T)>)->Result<DocValue,ToDocError>{
+    let mut build = Map::new();
+    for (key,value) in entries{
+        build.insert(key.clone(),value.tryToDocValue()?);
+    }
+    Ok(DocValue::Object(build))
+}
+
+impl<T:TryToDocValue,S> TryToDocValue for HashMap<String,T,S>{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        try_object(self.iter())
+    }
+}
+
+impl<T:TryToDocValue> TryToDocValue for BTreeMap<String,T>{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        try_object(self.iter())
+    }
+}
+
+impl<T:TryToDocValue> TryToDocValue for Map<String,T>{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        try_object(self.iter())
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl<T:TryToDocValue,S> TryToDocValue for indexmap::IndexMap<String,T,S>{
+    fn tryToDocValue(&self)->Result<DocValue,ToDocError>{
+        try_object(self.iter())
     }
 }
 
 impl DocValue{
     pub fn str(v:&str)->DocValue{
-        return DocValue::String(v.to_string());
+        DocValue::String(v.to_string())
     }
     pub fn string(v:String)->DocValue{
-        return DocValue::String(v);
+        DocValue::String(v)
     }
     pub fn num(i:i64)->DocValue{
-        return DocValue::Num(i);
+        DocValue::Num(i)
     }
     pub fn float(i:f64)->DocValue{
-        return DocValue::Float(i);
+        DocValue::Float(i)
     }
     pub fn object()->DocValue{
-        return DocValue::Object(HashMap::new());
+        DocValue::Object(Map::new())
     }
     pub fn vec()->DocValue{
-        return DocValue::Vec(vec![]);
+        DocValue::Vec(vec![])
     }
     pub fn binary()->DocValue{
-        return DocValue::Binary(vec![]);
+        DocValue::Binary(vec![])
     }
     pub fn bool(v:bool)->DocValue{
-        return DocValue::Bool(v);
+        DocValue::Bool(v)
     }
     //array
     pub fn push<T>(&mut self,v:T)
     where
         T:ToDocValue
     {
-        match self{
-            DocValue::Vec(p)=>{
-                p.push(v.toDocValue());
-            },
-            _=>{}
+        if let DocValue::Vec(p) = self{
+            p.push(v.toDocValue());
         }
     }
+    ///push for values that may not fit, e.g. a u64 above i64::MAX
+    pub fn try_push<T>(&mut self,v:T)->Result<(),ToDocError>
+    where
+        T:TryToDocValue
+    {
+        let value = v.tryToDocValue()?;
+        if let DocValue::Vec(p) = self{
+            p.push(value);
+        }
+        Ok(())
+    }
     pub fn vec_remove(&mut self,index:usize){
-        match self{
-            DocValue::Vec(p)=>{
-                p.remove(index);
-            },
-            _=>{}
+        if let DocValue::Vec(p) = self{
+            p.remove(index);
         }
     }
     //object
@@ -125,235 +416,260 @@ impl DocValue{
     {
         match self{
             DocValue::Object(d)=>{
-                return d.insert(k.to_string(),v.toDocValue());
+                d.insert(k.to_string(),v.toDocValue())
             },
             _=>{None}
         }
     }
+    ///insert for values that may not fit, e.g. a u64 above i64::MAX
+    pub fn try_insert<T>(&mut self,k:&str,v:T)->Result<Option<DocValue>,ToDocError>
+    where
+        T:TryToDocValue
+    {
+        let value = v.tryToDocValue()?;
+        match self{
+            DocValue::Object(d)=>{
+                Ok(d.insert(k.to_string(),value))
+            },
+            _=>{Ok(None)}
+        }
+    }
     pub fn contains_key(&self,key:&str)->Option<bool>{
         match self{
             DocValue::Object(v)=>{
-                return Some(v.contains_key(key));
+                Some(v.contains_key(key))
             },
             _=>{
-                return None;
+                None
             }
         }
     }
     pub fn object_remove(&mut self,key:&str)->Option<DocValue>{
         match self{
             DocValue::Object(v)=>{
-                return v.remove(key);
+                v.remove(key)
             },
             _=>{
-                return None;
+                None
             }
         }
     }
-    pub fn object_iter(&mut self)->Option<HashMapIter<String,DocValue>>{
+    pub fn object_iter(&mut self)->Option<MapIter<'_,String,DocValue>>{
         match self{
             DocValue::Object(v)=>{
-                return Some(v.iter());
+                Some(v.iter())
             },
             _=>{
-                return None;
+                None
             }
         }
     }
-    pub fn object_iter_mut(&mut self)->Option<HashMapIterMut<String,DocValue>>{
+    pub fn object_iter_mut(&mut self)->Option<MapIterMut<'_,String,DocValue>>{
         match self{
             DocValue::Object(v)=>{
-                return Some(v.iter_mut());
+                Some(v.iter_mut())
             },
             _=>{
-                return None;
+                None
             }
         }
     }
+    #[allow(clippy::result_unit_err)]
     pub fn update_path(&mut self,path:Vec<&str>,key:&str,value:DocValue)->Result<(),()>{
         match self{
-            DocValue::Object(v)=>{return update_path(
+            DocValue::Object(v)=>{update_path(
                 path,v,key,value
-            );},
-            _=>{return Err(());}
+            )},
+            _=>{Err(())}
         }
     }
     //updates
     pub fn update_string(&mut self,v:String){
-        match self{
-            DocValue::String(d)=>{
-                *d = v
-            },
-            _=>{}
+        if let DocValue::String(d) = self{
+            *d = v
         }
     }
     pub fn update_num(&mut self,v:i64){
-        match self{
-            DocValue::Num(d)=>{
-                *d = v
-            },
-            _=>{}
+        if let DocValue::Num(d) = self{
+            *d = v
         }
     }
     pub fn update_float(&mut self,v:f64){
-        match self{
-            DocValue::Float(d)=>{
-                *d = v
-            },
-            _=>{}
+        if let DocValue::Float(d) = self{
+            *d = v
         }
     }
     pub fn update_object(&mut self,k:&str,v:DocValue){
-        match self{
-            DocValue::Object(d)=>{
-                d.insert(k.to_string(), v);
-            },
-            _=>{}
+        if let DocValue::Object(d) = self{
+            d.insert(k.to_string(), v);
         }
     }
     pub fn update_vec(&mut self,v:Vec<DocValue>){
-        match self{
-            DocValue::Vec(d)=>{
-                *d = v;
-            },
-            _=>{}
+        if let DocValue::Vec(d) = self{
+            *d = v;
         }
     }
     pub fn update_binary(&mut self,v:Vec<u8>){
-        match self{
-            DocValue::Binary(d)=>{
-                *d = v;
-            },
-            _=>{}
+        if let DocValue::Binary(d) = self{
+            *d = v;
         }
     }
     pub fn update_bool(&mut self,v:bool){
-        match self{
-            DocValue::Bool(d)=>{
-                *d = v;
-            },
-            _=>{}
+        if let DocValue::Bool(d) = self{
+            *d = v;
         }
     }
     //extractors
-    pub fn as_object(&self)->Option<HashMap<String,DocValue>>{
+    pub fn as_object(&self)->Option<Map<String,DocValue>>{
         match self{
             DocValue::Object(v)=>{
-                return Some(v.clone());
+                Some(v.clone())
             },
             _=>{
-                return None;
+                None
             }
         }
     }
     pub fn as_vec(&self)->Option<Vec<DocValue>>{
         match self{
             DocValue::Vec(v)=>{
-                return Some(v.clone());
+                Some(v.clone())
             },
             _=>{
-                return None;
+                None
             }
         }
     }
     pub fn as_binary(&self)->Option<Vec<u8>>{
         match self{
             DocValue::Binary(v)=>{
-                return Some(v.clone());
+                Some(v.clone())
             },
             _=>{
-                return None;
+                None
             }
         }
     }
     pub fn as_string(&self)->Option<String>{
         match self{
             DocValue::String(v)=>{
-                return Some(v.clone());
+                Some(v.clone())
             },
             _=>{
-                return None;
+                None
             }
         }
     }
     pub fn as_num(&self)->Option<i64>{
         match self{
             DocValue::Num(v)=>{
-                return Some(v.clone());
+                Some(*v)
             },
             _=>{
-                return None;
+                None
             }
         }
     }
     pub fn as_float(&self)->Option<f64>{
         match self{
             DocValue::Float(v)=>{
-                return Some(v.clone());
+                Some(*v)
             },
             _=>{
-                return None;
+                None
             }
         }
     }
     pub fn as_bool(&self)->Option<bool>{
         match self{
             DocValue::Bool(v)=>{
-                return Some(v.clone());
+                Some(*v)
             },
             _=>{
-                return None;
+                None
             }
         }
     }
-    //checkers
-    pub fn self_is_object(&self)->bool{
+    //references
+    ///entry of a object by reference, None if missing or self is not a object
+    pub fn get(&self,key:&str)->Option<&DocValue>{
         match self{
-            DocValue::Object(_v)=>{return true;},
-            _=>{return false;}
+            DocValue::Object(v)=>{
+                v.get(key)
+            },
+            _=>{
+                None
+            }
         }
     }
-    pub fn self_is_vec(&self)->bool{
+    pub fn get_mut(&mut self,key:&str)->Option<&mut DocValue>{
         match self{
-            DocValue::Vec(_v)=>{return true;},
-            _=>{return false;}
+            DocValue::Object(v)=>{
+                v.get_mut(key)
+            },
+            _=>{
+                None
+            }
         }
     }
-    pub fn self_is_binary(&self)->bool{
+    ///entry of a vec by reference, None if out of bounds or self is not a vec
+    pub fn get_index(&self,index:usize)->Option<&DocValue>{
         match self{
-            DocValue::Binary(_v)=>{return true;},
-            _=>{return false;}
+            DocValue::Vec(v)=>{
+                v.get(index)
+            },
+            _=>{
+                None
+            }
         }
     }
-    pub fn self_is_string(&self)->bool{
+    pub fn get_index_mut(&mut self,index:usize)->Option<&mut DocValue>{
         match self{
-            DocValue::String(_v)=>{return true;},
-            _=>{return false;}
+            DocValue::Vec(v)=>{
+                v.get_mut(index)
+            },
+            _=>{
+                None
+            }
         }
     }
-    pub fn self_is_num(&self)->bool{
+    //checkers
+    ///name of the variant, used in FromDocError
+    pub fn type_name(&self)->&'static str{
         match self{
-            DocValue::Num(_v)=>{return true;},
-            _=>{return false;}
+            DocValue::Object(_)=>{"object"},
+            DocValue::Vec(_)=>{"vec"},
+            DocValue::Num(_)=>{"num"},
+            DocValue::String(_)=>{"string"},
+            DocValue::Float(_)=>{"float"},
+            DocValue::Binary(_)=>{"binary"},
+            DocValue::Null=>{"null"},
+            DocValue::Bool(_)=>{"bool"},
         }
     }
+    pub fn self_is_object(&self)->bool{
+        matches!(self,DocValue::Object(_))
+    }
+    pub fn self_is_vec(&self)->bool{
+        matches!(self,DocValue::Vec(_))
+    }
+    pub fn self_is_binary(&self)->bool{
+        matches!(self,DocValue::Binary(_))
+    }
+    pub fn self_is_string(&self)->bool{
+        matches!(self,DocValue::String(_))
+    }
+    pub fn self_is_num(&self)->bool{
+        matches!(self,DocValue::Num(_))
+    }
     pub fn self_is_float(&self)->bool{
-        match self{
-            DocValue::Float(_v)=>{return true;},
-            _=>{return false;}
-        }
+        matches!(self,DocValue::Float(_))
     }
     pub fn self_is_bool(&self)->bool{
-        match self{
-            DocValue::Bool(_v)=>{return true;},
-            _=>{return false;}
-        }
+        matches!(self,DocValue::Bool(_))
     }
     pub fn self_is_null(&self)->bool{
-        match self{
-            DocValue::Null=>{return true;},
-            _=>{return false;}
-        }
+        matches!(self,DocValue::Null)
     }
     //objecy key checks
     pub fn key_is_object(&self,key:&str)->bool{
@@ -448,38 +764,98 @@ impl DocValue{
     pub fn write(&self)->Vec<u8>{
         crate::writer::write(self)
     }
-    pub fn read(data:&Vec<u8>)->Result<DocValue,()>{
-        let mut reader = crate::reader::Reader::new(data);
-        reader.build()
+    ///
+    /// writes a header with magic bytes, format version and flags before
+    /// the document so it can be recognised, read accepts both forms.
+    ///
+    pub fn write_with_header(&self)->Vec<u8>{
+        crate::writer::write_with(self,crate::EncodeOptions{header:true,..crate::EncodeOptions::default()})
+    }
+    ///
+    /// writes the same bytes for the same document in every process, keys
+    /// are sorted and floats normalised, see EncodeOptions::canonical.
+    ///
+    pub fn write_canonical(&self)->Vec<u8>{
+        crate::writer::write_with(self,crate::EncodeOptions{canonical:true,..crate::EncodeOptions::default()})
+    }
+    ///writes the document in the format set in options
+    pub fn write_with(&self,options:crate::EncodeOptions)->Vec<u8>{
+        crate::writer::write_with(self,options)
+    }
+    ///
+    /// streams the same bytes as write into out, small writes are issued
+    /// per value so wrap unbuffered files and sockets in a BufWriter.
+    ///
+    pub fn write_to<W:std::io::Write>(&self,out:&mut W)->std::io::Result<()>{
+        crate::writer::write_to(self,out)
+    }
+    pub fn write_to_with<W:std::io::Write>(&self,out:&mut W,options:crate::EncodeOptions)->std::io::Result<()>{
+        crate::writer::write_to_with(self,out,options)
+    }
+    pub fn read(data:&[u8])->Result<DocValue,crate::DecodeError>{
+        DocValue::read_with(data,crate::DecodeOptions::default())
+    }
+    pub fn read_with(data:&[u8],options:crate::DecodeOptions)->Result<DocValue,crate::DecodeError>{
+        #[cfg(feature = "compression")]
+        if let Some(data) = crate::compress::decompress(data,options)?{
+            let view = crate::DocRef::read_with(&data,options)?;
+            return Ok(view.to_owned());
+        }
+        let view = crate::DocRef::read_with(data,options)?;
+        Ok(view.to_owned())
+    }
+    ///
+    /// reads one document from a stream and leaves it positioned after
+    /// the document, returns DecodeError::Eof if the stream is already at
+    /// its end and DecodeError::Truncated if it ends inside the document.
+    ///
+    pub fn read_from<R:std::io::Read>(r:&mut R)->Result<DocValue,crate::DecodeError>{
+        DocValue::read_from_with(r,crate::DecodeOptions::default())
+    }
+    pub fn read_from_with<R:std::io::Read>(r:&mut R,options:crate::DecodeOptions)->Result<DocValue,crate::DecodeError>{
+        let data = crate::reader::read_from(r,options)?;
+        DocValue::read_with(&data,options)
+    }
+    ///
+    /// writes the document as json, num and float stay apart and binary,
+    /// nan and infinities are written as tagged objects like {"$binary":"AQID"}
+    /// so from_json reads back the same document.
+    ///
+    pub fn to_json(&self)->String{
+        crate::json::to_json(self,crate::JsonOptions::default())
+    }
+    pub fn to_json_with(&self,options:crate::JsonOptions)->String{
+        crate::json::to_json(self,options)
+    }
+    ///parses json written by to_json or any other json text
+    pub fn from_json(text:&str)->Result<DocValue,crate::JsonError>{
+        DocValue::from_json_with(text,crate::DecodeOptions::default())
+    }
+    ///parses json with the depth, length and entry limits of options
+    pub fn from_json_with(text:&str,options:crate::DecodeOptions)->Result<DocValue,crate::JsonError>{
+        crate::json::from_json(text,options)
     }
 }
 
 fn update_path(
     path:Vec<&str>,
-    pool:&mut HashMap<String,DocValue>,
+    pool:&mut Map<String,DocValue>,
     key:&str,
     value:DocValue
 )->Result<(),()>{
 
-    if path.len() == 0{
+    if path.is_empty(){
         return Err(());
     }
 
     // let mut base = HashMap::new();
-    let mut hold:&mut HashMap<String,DocValue> = pool;
+    let mut hold:&mut Map<String,DocValue> = pool;
     for item in path{
         match pool.get_mut(item){
-            Some(v)=>{
-                match v{
-                    DocValue::Object(s)=>{
-                        hold = s;
-                    },
-                    _=>{
-                        return Err(());
-                    }
-                }
+            Some(DocValue::Object(s))=>{
+                hold = s;
             },
-            None=>{
+            _=>{
                 return Err(());
             }
         }
@@ -487,6 +863,6 @@ fn update_path(
 
     hold.insert(key.to_string(), value);
 
-    return Ok(());
+    Ok(())
 
 }
\ No newline at end of file
@@ -0,0 +1,119 @@
+use crate::{DocValue,DecodeError,DecodeOptions,Map};
+
+///
+/// borrowed view of a encoded document, strings and binaries point into
+/// the input buffer instead of being copied.
+///
+/// let bin = person.write();
+/// let view = DocRef::read(&bin).unwrap();
+/// let name:&str = view.get("name").unwrap().as_string().unwrap();
+/// let owned:DocValue = view.to_owned();
+///
+#[derive(Clone,Debug)]
+pub enum DocRef<'a>{
+    Object(Map<&'a str,DocRef<'a>>),Vec(Vec<DocRef<'a>>),Num(i64),String(&'a str),Float(f64),Binary(&'a [u8]),Null,Bool(bool)
+}
+
+impl<'a> DocRef<'a>{
+    //doc functions
+    pub fn read(data:&'a [u8])->Result<DocRef<'a>,DecodeError>{
+        DocRef::read_with(data,DecodeOptions::default())
+    }
+    pub fn read_with(data:&'a [u8],options:DecodeOptions)->Result<DocRef<'a>,DecodeError>{
+        let mut reader = crate::reader::Reader::new(data,options);
+        reader.build()
+    }
+    ///copies the view into a owned DocValue
+    pub fn to_owned(&self)->DocValue{
+        match self{
+            DocRef::Object(v)=>{
+                let mut map = Map::with_capacity(v.len());
+                for (key,value) in v.iter(){
+                    map.insert(key.to_string(),value.to_owned());
+                }
+                DocValue::Object(map)
+            },
+            DocRef::Vec(v)=>{DocValue::Vec(v.iter().map(|i|i.to_owned()).collect())},
+            DocRef::Num(v)=>{DocValue::Num(*v)},
+            DocRef::String(v)=>{DocValue::String(v.to_string())},
+            DocRef::Float(v)=>{DocValue::Float(*v)},
+            DocRef::Binary(v)=>{DocValue::Binary(v.to_vec())},
+            DocRef::Null=>{DocValue::Null},
+            DocRef::Bool(v)=>{DocValue::Bool(*v)},
+        }
+    }
+    //object
+    pub fn get(&self,key:&str)->Option<&DocRef<'a>>{
+        match self{
+            DocRef::Object(v)=>{v.get(key)},
+            _=>{None}
+        }
+    }
+    pub fn contains_key(&self,key:&str)->Option<bool>{
+        match self{
+            DocRef::Object(v)=>{Some(v.contains_key(key))},
+            _=>{None}
+        }
+    }
+    //extractors
+    pub fn as_object(&self)->Option<&Map<&'a str,DocRef<'a>>>{
+        match self{
+            DocRef::Object(v)=>{Some(v)},
+            _=>{None}
+        }
+    }
+    pub fn as_vec(&self)->Option<&[DocRef<'a>]>{
+        match self{
+            DocRef::Vec(v)=>{Some(v)},
+            _=>{None}
+        }
+    }
+    pub fn as_binary(&self)->Option<&'a [u8]>{
+        match self{
+            DocRef::Binary(v)=>{Some(v)},
+            _=>{None}
+        }
+    }
+    pub fn as_string(&self)->Option<&'a str>{
+        match self{
+            DocRef::String(v)=>{Some(v)},
+            _=>{None}
+        }
+    }
+    pub fn as_num(&self)->Option<i64>{
+        match self{
+            DocRef::Num(v)=>{Some(*v)},
+            _=>{None}
+        }
+    }
+    pub fn as_float(&self)->Option<f64>{
+        match self{
+            DocRef::Float(v)=>{Some(*v)},
+            _=>{None}
+        }
+    }
+    pub fn as_bool(&self)->Option<bool>{
+        match self{
+            DocRef::Bool(v)=>{Some(*v)},
+            _=>{None}
+        }
+    }
+    //checkers
+    pub fn self_is_object(&self)->bool{matches!(self,DocRef::Object(_))}
+    pub fn self_is_vec(&self)->bool{matches!(self,DocRef::Vec(_))}
+    pub fn self_is_binary(&self)->bool{matches!(self,DocRef::Binary(_))}
+    pub fn self_is_string(&self)->bool{matches!(self,DocRef::String(_))}
+    pub fn self_is_num(&self)->bool{matches!(self,DocRef::Num(_))}
+    pub fn self_is_float(&self)->bool{matches!(self,DocRef::Float(_))}
+    pub fn self_is_bool(&self)->bool{matches!(self,DocRef::Bool(_))}
+    pub fn self_is_null(&self)->bool{matches!(self,DocRef::Null)}
+    //object key checks
+    pub fn key_is_object(&self,key:&str)->bool{self.get(key).is_some_and(|d|d.self_is_object())}
+    pub fn key_is_vec(&self,key:&str)->bool{self.get(key).is_some_and(|d|d.self_is_vec())}
+    pub fn key_is_binary(&self,key:&str)->bool{self.get(key).is_some_and(|d|d.self_is_binary())}
+    pub fn key_is_string(&self,key:&str)->bool{self.get(key).is_some_and(|d|d.self_is_string())}
+    pub fn key_is_num(&self,key:&str)->bool{self.get(key).is_some_and(|d|d.self_is_num())}
+    pub fn key_is_float(&self,key:&str)->bool{self.get(key).is_some_and(|d|d.self_is_float())}
+    pub fn key_is_bool(&self,key:&str)->bool{self.get(key).is_some_and(|d|d.self_is_bool())}
+    pub fn key_is_null(&self,key:&str)->bool{self.get(key).is_some_and(|d|d.self_is_null())}
+}
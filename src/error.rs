@@ -0,0 +1,310 @@
+use std::{fmt,io};
+
+///
+/// error returned when a byte buffer can not be decoded into a DocValue.
+///
+/// every variant carries the byte offset into the input where decoding
+/// failed and the document path of the value being decoded, keys are
+/// joined with "." and vec indexes are written as "[i]" e.g.
+/// "sports.match.scores[2]", the root value has an empty path.
+///
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DecodeError{
+    /// input ended before the value was complete
+    Truncated{offset:usize,path:String},
+    /// data type byte is not a known type
+    UnknownType{tag:u8,offset:usize,path:String},
+    /// continue byte after an object or vec entry was not 0 or 1
+    BadContinueByte{byte:u8,offset:usize,path:String},
+    /// object key is not valid utf8
+    InvalidKey{offset:usize,path:String},
+    /// string value is not valid utf8
+    InvalidString{offset:usize,path:String},
+    /// num payload is not 8 bytes
+    InvalidNum{offset:usize,path:String},
+    /// float payload is not 8 bytes
+    InvalidFloat{offset:usize,path:String},
+    /// bool payload is not a single 0 or 1 byte
+    InvalidBool{offset:usize,path:String},
+    /// null payload is not a single 0 byte
+    InvalidNull{offset:usize,path:String},
+    /// length prefix does not fit in memory addresses of this platform
+    LengthOverflow{len:u64,offset:usize,path:String},
+    /// bytes left over after a value, inside its frame or after the root
+    TrailingBytes{offset:usize,path:String},
+    /// entry count of a counted object or vec does not match its bytes
+    CountMismatch{count:usize,offset:usize,path:String},
+    /// checksum trailer does not match the document bytes
+    ChecksumMismatch{expected:u32,found:u32,offset:usize},
+    /// document is compressed and can only be read into a owned DocValue
+    Compressed{offset:usize},
+    /// compressed block does not decompress to its stated length
+    InvalidCompressed{offset:usize},
+    /// a limit set in DecodeOptions was hit
+    LimitExceeded{limit:Limit,offset:usize,path:String},
+    /// header names a format version newer than this reader
+    UnsupportedVersion{version:u8,offset:usize},
+    /// header sets flags this reader does not understand
+    UnsupportedFlags{flags:u8,offset:usize},
+    /// stream ended before the first byte of a document
+    Eof,
+    /// reading from the stream failed
+    Io{error:io::Error,offset:usize},
+}
+
+///
+/// limit of DecodeOptions that was exceeded
+///
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Limit{
+    /// DecodeOptions::max_depth
+    Depth,
+    /// DecodeOptions::max_len
+    Length,
+    /// DecodeOptions::max_entries
+    Entries,
+    /// DecodeOptions::max_total_bytes
+    TotalBytes,
+}
+
+impl fmt::Display for Limit{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self{
+            Limit::Depth=>{write!(f,"max_depth")},
+            Limit::Length=>{write!(f,"max_len")},
+            Limit::Entries=>{write!(f,"max_entries")},
+            Limit::TotalBytes=>{write!(f,"max_total_bytes")},
+        }
+    }
+}
+
+impl DecodeError{
+    /// byte offset into the input where decoding failed
+    pub fn offset(&self)->usize{
+        match self{
+            DecodeError::Truncated{offset,..}=>{*offset},
+            DecodeError::UnknownType{offset,..}=>{*offset},
+            DecodeError::BadContinueByte{offset,..}=>{*offset},
+            DecodeError::InvalidKey{offset,..}=>{*offset},
+            DecodeError::InvalidString{offset,..}=>{*offset},
+            DecodeError::InvalidNum{offset,..}=>{*offset},
+            DecodeError::InvalidFloat{offset,..}=>{*offset},
+            DecodeError::InvalidBool{offset,..}=>{*offset},
+            DecodeError::InvalidNull{offset,..}=>{*offset},
+            DecodeError::LengthOverflow{offset,..}=>{*offset},
+            DecodeError::TrailingBytes{offset,..}=>{*offset},
+            DecodeError::CountMismatch{offset,..}=>{*offset},
+            DecodeError::ChecksumMismatch{offset,..}=>{*offset},
+            DecodeError::Compressed{offset}=>{*offset},
+            DecodeError::InvalidCompressed{offset}=>{*offset},
+            DecodeError::LimitExceeded{offset,..}=>{*offset},
+            DecodeError::UnsupportedVersion{offset,..}=>{*offset},
+            DecodeError::UnsupportedFlags{offset,..}=>{*offset},
+            DecodeError::Eof=>{0},
+            DecodeError::Io{offset,..}=>{*offset},
+        }
+    }
+    /// document path of the value being decoded
+    pub fn path(&self)->&str{
+        match self{
+            DecodeError::Truncated{path,..}=>{path},
+            DecodeError::UnknownType{path,..}=>{path},
+            DecodeError::BadContinueByte{path,..}=>{path},
+            DecodeError::InvalidKey{path,..}=>{path},
+            DecodeError::InvalidString{path,..}=>{path},
+            DecodeError::InvalidNum{path,..}=>{path},
+            DecodeError::InvalidFloat{path,..}=>{path},
+            DecodeError::InvalidBool{path,..}=>{path},
+            DecodeError::InvalidNull{path,..}=>{path},
+            DecodeError::LengthOverflow{path,..}=>{path},
+            DecodeError::TrailingBytes{path,..}=>{path},
+            DecodeError::CountMismatch{path,..}=>{path},
+            DecodeError::LimitExceeded{path,..}=>{path},
+            DecodeError::ChecksumMismatch{..}=>{""},
+            DecodeError::Compressed{..} | DecodeError::InvalidCompressed{..}=>{""},
+            DecodeError::UnsupportedVersion{..} | DecodeError::UnsupportedFlags{..}=>{""},
+            DecodeError::Eof | DecodeError::Io{..}=>{""},
+        }
+    }
+}
+
+impl fmt::Display for DecodeError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self{
+            DecodeError::Truncated{..}=>{write!(f,"truncated input")?;},
+            DecodeError::UnknownType{tag,..}=>{write!(f,"unknown data type {}",tag)?;},
+            DecodeError::BadContinueByte{byte,..}=>{write!(f,"bad continue byte {}",byte)?;},
+            DecodeError::InvalidKey{..}=>{write!(f,"invalid utf8 in object key")?;},
+            DecodeError::InvalidString{..}=>{write!(f,"invalid utf8 in string")?;},
+            DecodeError::InvalidNum{..}=>{write!(f,"invalid num payload")?;},
+            DecodeError::InvalidFloat{..}=>{write!(f,"invalid float payload")?;},
+            DecodeError::InvalidBool{..}=>{write!(f,"invalid bool payload")?;},
+            DecodeError::InvalidNull{..}=>{write!(f,"invalid null payload")?;},
+            DecodeError::LengthOverflow{len,..}=>{write!(f,"length {} overflows",len)?;},
+            DecodeError::TrailingBytes{..}=>{write!(f,"trailing bytes")?;},
+            DecodeError::CountMismatch{count,..}=>{write!(f,"entry count {} does not match data length",count)?;},
+            DecodeError::ChecksumMismatch{expected,found,..}=>{write!(f,"checksum mismatch, expected {:#010x} found {:#010x}",expected,found)?;},
+            DecodeError::Compressed{..}=>{write!(f,"compressed document can not be borrowed")?;},
+            DecodeError::InvalidCompressed{..}=>{write!(f,"invalid compressed block")?;},
+            DecodeError::LimitExceeded{limit,..}=>{write!(f,"{} exceeded",limit)?;},
+            DecodeError::UnsupportedVersion{version,..}=>{write!(f,"unsupported format version {}",version)?;},
+            DecodeError::UnsupportedFlags{flags,..}=>{write!(f,"unsupported header flags {:#010b}",flags)?;},
+            DecodeError::Eof=>{return write!(f,"end of stream");},
+            DecodeError::Io{error,..}=>{write!(f,"io error: {}",error)?;},
+        }
+        write!(f," at byte {}",self.offset())?;
+        if !self.path().is_empty(){
+            write!(f," (path: {})",self.path())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DecodeError{
+    fn source(&self)->Option<&(dyn std::error::Error + 'static)>{
+        match self{
+            DecodeError::Io{error,..}=>{Some(error)},
+            _=>{None}
+        }
+    }
+}
+
+///
+/// error returned by to_bytes and from_bytes of the serde feature
+///
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SerdeError{
+    /// error raised by a Serialize or Deserialize impl or a value that does
+    /// not fit the document types e.g. a u64 above i64::MAX
+    Message(String),
+    /// map key is not a string, char, integer or unit variant
+    KeyNotString,
+    /// input is not a valid document
+    Decode(DecodeError),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for SerdeError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self{
+            SerdeError::Message(message)=>{write!(f,"{}",message)},
+            SerdeError::KeyNotString=>{write!(f,"map key must be a string")},
+            SerdeError::Decode(error)=>{write!(f,"{}",error)},
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for SerdeError{
+    fn source(&self)->Option<&(dyn std::error::Error + 'static)>{
+        match self{
+            SerdeError::Decode(error)=>{Some(error)},
+            _=>{None}
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<DecodeError> for SerdeError{
+    fn from(error:DecodeError)->SerdeError{
+        SerdeError::Decode(error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for SerdeError{
+    fn custom<T:fmt::Display>(message:T)->SerdeError{
+        SerdeError::Message(message.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for SerdeError{
+    fn custom<T:fmt::Display>(message:T)->SerdeError{
+        SerdeError::Message(message.to_string())
+    }
+}
+
+///
+/// error returned by DocValue::from_json, offset is the byte offset into
+/// the json text where parsing failed.
+///
+#[derive(Debug,Clone,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum JsonError{
+    /// unexpected character or end of input, expected names what was expected
+    Syntax{expected:&'static str,offset:usize},
+    /// number does not follow the json number grammar
+    InvalidNumber{offset:usize},
+    /// unknown escape or a \u escape that is not a unicode scalar value
+    InvalidEscape{offset:usize},
+    /// "$binary" or "$float" tag holds a value of the wrong shape
+    InvalidTag{offset:usize},
+    /// characters left over after the root value
+    TrailingCharacters{offset:usize},
+    /// a limit set in DecodeOptions was hit
+    LimitExceeded{limit:Limit,offset:usize},
+}
+
+impl JsonError{
+    /// byte offset into the json text where parsing failed
+    pub fn offset(&self)->usize{
+        match self{
+            JsonError::Syntax{offset,..}=>{*offset},
+            JsonError::InvalidNumber{offset}=>{*offset},
+            JsonError::InvalidEscape{offset}=>{*offset},
+            JsonError::InvalidTag{offset}=>{*offset},
+            JsonError::TrailingCharacters{offset}=>{*offset},
+            JsonError::LimitExceeded{offset,..}=>{*offset},
+        }
+    }
+}
+
+impl fmt::Display for JsonError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self{
+            JsonError::Syntax{expected,..}=>{write!(f,"expected {}",expected)?;},
+            JsonError::InvalidNumber{..}=>{write!(f,"invalid number")?;},
+            JsonError::InvalidEscape{..}=>{write!(f,"invalid escape")?;},
+            JsonError::InvalidTag{..}=>{write!(f,"invalid tagged value")?;},
+            JsonError::TrailingCharacters{..}=>{write!(f,"trailing characters")?;},
+            JsonError::LimitExceeded{limit,..}=>{write!(f,"{} exceeded",limit)?;},
+        }
+        write!(f," at byte {}",self.offset())
+    }
+}
+
+impl std::error::Error for JsonError{}
+
+///
+/// error returned by DocValue::pointer_set, path is the json pointer of
+/// the value that could not be used.
+///
+#[derive(Debug,Clone,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum PointerError{
+    /// pointer is not empty and does not start with '/', or has a '~' not followed by 0 or 1
+    Invalid{pointer:String},
+    /// parent of the value to set does not exist
+    NotFound{path:String},
+    /// parent of the value to set is not a object or vec
+    NotContainer{path:String},
+    /// last token is not a index of the vec or the index after its last entry
+    InvalidIndex{path:String},
+}
+
+impl fmt::Display for PointerError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        match self{
+            PointerError::Invalid{pointer}=>{write!(f,"invalid json pointer {:?}",pointer)},
+            PointerError::NotFound{path}=>{write!(f,"no value at {:?}",path)},
+            PointerError::NotContainer{path}=>{write!(f,"value at {:?} is not a object or vec",path)},
+            PointerError::InvalidIndex{path}=>{write!(f,"invalid vec index in {:?}",path)},
+        }
+    }
+}
+
+impl std::error::Error for PointerError{}
@@ -0,0 +1,384 @@
+use crate::{DocValue,Map};
+use std::{fmt,collections::{BTreeMap,HashMap},hash::BuildHasher};
+
+///
+/// builds a value back from a DocValue, the counterpart of ToDocValue.
+///
+/// let age = i64::fromDocValue(&doc)?;
+///
+#[allow(non_snake_case)]
+pub trait FromDocValue:Sized{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>;
+    ///
+    /// reads a Vec<Self>, a vec by default. u8 reads binary instead so
+    /// Vec<u8> stays binary while Vec<T> is a vec for every other T.
+    ///
+    #[doc(hidden)]
+    fn fromDocVec(value:&DocValue)->Result<Vec<Self>,FromDocError>{
+        match value{
+            DocValue::Vec(pool)=>{
+                let mut build = Vec::with_capacity(pool.len());
+                for (index,item) in pool.iter().enumerate(){
+                    build.push(Self::fromDocValue(item).map_err(|e|e.with_index(index))?);
+                }
+                Ok(build)
+            },
+            _=>{Err(FromDocError::invalid_type("vec",value))}
+        }
+    }
+}
+
+///
+/// error returned by FromDocValue when a DocValue does not have the shape
+/// of the requested type. path is written like the path of DecodeError
+/// e.g. "sports.match.scores[2]" and is empty for the value itself.
+///
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct FromDocError{
+    path:String,
+    expected:String,
+    found:String,
+}
+
+impl FromDocError{
+    pub fn new(expected:&str,found:&str)->FromDocError{
+        FromDocError{path:String::new(),expected:expected.to_string(),found:found.to_string()}
+    }
+    ///value has the wrong variant
+    pub fn invalid_type(expected:&str,found:&DocValue)->FromDocError{
+        FromDocError::new(expected,found.type_name())
+    }
+    ///object key is missing
+    pub fn missing(expected:&str)->FromDocError{
+        FromDocError::new(expected,"nothing")
+    }
+    ///document path of the value that failed
+    pub fn path(&self)->&str{
+        &self.path
+    }
+    pub fn expected(&self)->&str{
+        &self.expected
+    }
+    pub fn found(&self)->&str{
+        &self.found
+    }
+    ///prepends a object key to the path, used by containers on errors of their entries
+    pub fn with_key(mut self,key:&str)->FromDocError{
+        if self.path.is_empty() || self.path.starts_with('['){
+            self.path = format!("{}{}",key,self.path);
+        } else {
+            self.path = format!("{}.{}",key,self.path);
+        }
+        self
+    }
+    ///prepends a vec index to the path
+    pub fn with_index(mut self,index:usize)->FromDocError{
+        if self.path.is_empty() || self.path.starts_with('['){
+            self.path = format!("[{}]{}",index,self.path);
+        } else {
+            self.path = format!("[{}].{}",index,self.path);
+        }
+        self
+    }
+}
+
+impl fmt::Display for FromDocError{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        write!(f,"expected {} found {}",self.expected,self.found)?;
+        if !self.path.is_empty(){
+            write!(f," (path: {})",self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FromDocError{}
+
+impl FromDocValue for DocValue{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        Ok(value.clone())
+    }
+}
+
+impl FromDocValue for bool{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Bool(v)=>{Ok(*v)},
+            _=>{Err(FromDocError::invalid_type("bool",value))}
+        }
+    }
+}
+
+impl FromDocValue for String{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::String(v)=>{Ok(v.clone())},
+            _=>{Err(FromDocError::invalid_type("string",value))}
+        }
+    }
+}
+
+impl FromDocValue for char{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::String(v)=>{
+                let mut chars = v.chars();
+                match (chars.next(),chars.next()){
+                    (Some(c),None)=>{Ok(c)},
+                    _=>{Err(FromDocError::new("char",&format!("string of {} chars",v.chars().count())))}
+                }
+            },
+            _=>{Err(FromDocError::invalid_type("string",value))}
+        }
+    }
+}
+
+impl FromDocValue for i64{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Num(v)=>{Ok(*v)},
+            _=>{Err(FromDocError::invalid_type("num",value))}
+        }
+    }
+}
+
+///integers read from a num, a num outside the range of the type is an error
+macro_rules! from_num{
+    ($($t:ty),*)=>{
+        $(
+            impl FromDocValue for $t{
+                fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+                    let num = i64::fromDocValue(value)?;
+                    match <$t>::try_from(num){
+                        Ok(v)=>{Ok(v)},
+                        Err(_)=>{Err(FromDocError::new(stringify!($t),&format!("num {}",num)))}
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_num!(i8,i16,i32,i128,isize,u16,u32,u64,u128,usize);
+
+impl FromDocValue for u8{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        let num = i64::fromDocValue(value)?;
+        match u8::try_from(num){
+            Ok(v)=>{Ok(v)},
+            Err(_)=>{Err(FromDocError::new("u8",&format!("num {}",num)))}
+        }
+    }
+    fn fromDocVec(value:&DocValue)->Result<Vec<Self>,FromDocError>{
+        match value{
+            DocValue::Binary(v)=>{Ok(v.clone())},
+            _=>{Err(FromDocError::invalid_type("binary",value))}
+        }
+    }
+}
+
+impl FromDocValue for f64{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Float(v)=>{Ok(*v)},
+            _=>{Err(FromDocError::invalid_type("float",value))}
+        }
+    }
+}
+
+impl FromDocValue for f32{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        Ok(f64::fromDocValue(value)? as f32)
+    }
+}
+
+impl FromDocValue for (){
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Null=>{Ok(())},
+            _=>{Err(FromDocError::invalid_type("null",value))}
+        }
+    }
+}
+
+///null is None, every other value is read as T
+impl<T:FromDocValue> FromDocValue for Option<T>{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Null=>{Ok(None)},
+            _=>{Ok(Some(T::fromDocValue(value)?))}
+        }
+    }
+}
+
+impl<T:FromDocValue> FromDocValue for Box<T>{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        Ok(Box::new(T::fromDocValue(value)?))
+    }
+}
+
+///a vec, or binary for Vec<u8>
+impl<T:FromDocValue> FromDocValue for Vec<T>{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        T::fromDocVec(value)
+    }
+}
+
+impl<T:FromDocValue,S:BuildHasher + Default> FromDocValue for HashMap<String,T,S>{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Object(map)=>{
+                let mut build = HashMap::with_capacity_and_hasher(map.len(),S::default());
+                for (key,item) in map.iter(){
+                    build.insert(key.clone(),T::fromDocValue(item).map_err(|e|e.with_key(key))?);
+                }
+                Ok(build)
+            },
+            _=>{Err(FromDocError::invalid_type("object",value))}
+        }
+    }
+}
+
+impl<T:FromDocValue> FromDocValue for BTreeMap<String,T>{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Object(map)=>{
+                let mut build = BTreeMap::new();
+                for (key,item) in map.iter(){
+                    build.insert(key.clone(),T::fromDocValue(item).map_err(|e|e.with_key(key))?);
+                }
+                Ok(build)
+            },
+            _=>{Err(FromDocError::invalid_type("object",value))}
+        }
+    }
+}
+
+impl<T:FromDocValue> FromDocValue for Map<String,T>{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Object(map)=>{
+                let mut build = Map::with_capacity(map.len());
+                for (key,item) in map.iter(){
+                    build.insert(key.clone(),T::fromDocValue(item).map_err(|e|e.with_key(key))?);
+                }
+                Ok(build)
+            },
+            _=>{Err(FromDocError::invalid_type("object",value))}
+        }
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl<T:FromDocValue,S:BuildHasher + Default> FromDocValue for indexmap::IndexMap<String,T,S>{
+    fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+        match value{
+            DocValue::Object(map)=>{
+                let mut build = indexmap::IndexMap::with_capacity_and_hasher(map.len(),S::default());
+                for (key,item) in map.iter(){
+                    build.insert(key.clone(),T::fromDocValue(item).map_err(|e|e.with_key(key))?);
+                }
+                Ok(build)
+            },
+            _=>{Err(FromDocError::invalid_type("object",value))}
+        }
+    }
+}
+
+///tuples read from a vec with exactly as many entries
+macro_rules! from_tuple{
+    ($len:expr=>$($t:ident $index:tt),*)=>{
+        impl<$($t:FromDocValue),*> FromDocValue for ($($t,)*){
+            fn fromDocValue(value:&DocValue)->Result<Self,FromDocError>{
+                match value{
+                    DocValue::Vec(pool) if pool.len() == $len=>{
+                        Ok(($($t::fromDocValue(&pool[$index]).map_err(|e|e.with_index($index))?,)*))
+                    },
+                    DocValue::Vec(pool)=>{
+                        Err(FromDocError::new(concat!("vec of ",$len),&format!("vec of {}",pool.len())))
+                    },
+                    _=>{Err(FromDocError::invalid_type("vec",value))}
+                }
+            }
+        }
+        impl<$($t:FromDocValue),*> TryFrom<&DocValue> for ($($t,)*){
+            type Error = FromDocError;
+            fn try_from(value:&DocValue)->Result<Self,FromDocError>{
+                Self::fromDocValue(value)
+            }
+        }
+    };
+}
+
+from_tuple!(1=>A 0);
+from_tuple!(2=>A 0,B 1);
+from_tuple!(3=>A 0,B 1,C 2);
+from_tuple!(4=>A 0,B 1,C 2,D 3);
+from_tuple!(5=>A 0,B 1,C 2,D 3,E 4);
+from_tuple!(6=>A 0,B 1,C 2,D 3,E 4,F 5);
+from_tuple!(7=>A 0,B 1,C 2,D 3,E 4,F 5,G 6);
+from_tuple!(8=>A 0,B 1,C 2,D 3,E 4,F 5,G 6,H 7);
+
+///
+/// TryFrom<&DocValue> for every FromDocValue type of this crate
+///
+/// let age = i64::try_from(&doc)?;
+/// let scores:Vec<f64> = (&doc).try_into()?;
+///
+macro_rules! try_from{
+    ($($t:ty),*)=>{
+        $(
+            impl TryFrom<&DocValue> for $t{
+                type Error = FromDocError;
+                fn try_from(value:&DocValue)->Result<Self,FromDocError>{
+                    <$t>::fromDocValue(value)
+                }
+            }
+        )*
+    };
+}
+
+try_from!(bool,String,char,i8,i16,i32,i64,i128,isize,u8,u16,u32,u64,u128,usize,f32,f64,());
+
+impl<T:FromDocValue> TryFrom<&DocValue> for Option<T>{
+    type Error = FromDocError;
+    fn try_from(value:&DocValue)->Result<Self,FromDocError>{
+        Self::fromDocValue(value)
+    }
+}
+
+impl<T:FromDocValue> TryFrom<&DocValue> for Vec<T>{
+    type Error = FromDocError;
+    fn try_from(value:&DocValue)->Result<Self,FromDocError>{
+        Self::fromDocValue(value)
+    }
+}
+
+impl<T:FromDocValue,S:BuildHasher + Default> TryFrom<&DocValue> for HashMap<String,T,S>{
+    type Error = FromDocError;
+    fn try_from(value:&DocValue)->Result<Self,FromDocError>{
+        Self::fromDocValue(value)
+    }
+}
+
+impl<T:FromDocValue> TryFrom<&DocValue> for BTreeMap<String,T>{
+    type Error = FromDocError;
+    fn try_from(value:&DocValue)->Result<Self,FromDocError>{
+        Self::fromDocValue(value)
+    }
+}
+
+impl<T:FromDocValue> TryFrom<&DocValue> for Map<String,T>{
+    type Error = FromDocError;
+    fn try_from(value:&DocValue)->Result<Self,FromDocError>{
+        Self::fromDocValue(value)
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl<T:FromDocValue,S:BuildHasher + Default> TryFrom<&DocValue> for indexmap::IndexMap<String,T,S>{
+    type Error = FromDocError;
+    fn try_from(value:&DocValue)->Result<Self,FromDocError>{
+        Self::fromDocValue(value)
+    }
+}
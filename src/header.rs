@@ -0,0 +1,59 @@
+use crate::DecodeError;
+
+///first bytes of a document written with a header
+pub const MAGIC:[u8;4] = *b"GZBD";
+
+///latest format version this crate writes and reads
+pub const VERSION:u8 = 2;
+
+///header length, magic version and flags
+pub const HEADER_LEN:usize = 6;
+
+///objects and vecs store their entry count up front instead of continue bytes
+pub const FLAG_COUNTED:u8 = 0b0000_0001;
+
+///a crc32c of the header and data follows the root data_line
+pub const FLAG_CHECKSUM:u8 = 0b0000_0010;
+
+///the root data_line is stored as a lz4 block, needs the compression feature
+pub const FLAG_COMPRESSED:u8 = 0b0000_0100;
+
+///flags understood by this version of the reader
+#[cfg(feature = "compression")]
+pub const KNOWN_FLAGS:u8 = FLAG_COUNTED | FLAG_CHECKSUM | FLAG_COMPRESSED;
+#[cfg(not(feature = "compression"))]
+pub const KNOWN_FLAGS:u8 = FLAG_COUNTED | FLAG_CHECKSUM;
+
+///
+/// optional header in front of a document, a document without header
+/// starts with a data_type byte which is never the first magic byte so
+/// both can be told apart and read by the same reader.
+///
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub struct Header{
+    pub version:u8,
+    pub flags:u8,
+}
+
+impl Header{
+    pub fn new(version:u8,flags:u8)->Header{
+        Header{version,flags}
+    }
+    pub fn to_bytes(self)->[u8;HEADER_LEN]{
+        [MAGIC[0],MAGIC[1],MAGIC[2],MAGIC[3],self.version,self.flags]
+    }
+    ///parses the header at the start of data, Ok(None) if data has no header
+    pub fn parse(data:&[u8])->Result<Option<Header>,DecodeError>{
+        if data.len() < HEADER_LEN || data[..MAGIC.len()] != MAGIC{
+            return Ok(None);
+        }
+        let header = Header::new(data[4],data[5]);
+        if header.version == 0 || header.version > VERSION{
+            return Err(DecodeError::UnsupportedVersion{version:header.version,offset:4});
+        }
+        if header.flags & !KNOWN_FLAGS != 0{
+            return Err(DecodeError::UnsupportedFlags{flags:header.flags,offset:5});
+        }
+        Ok(Some(header))
+    }
+}
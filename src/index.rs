@@ -0,0 +1,74 @@
+use crate::{DocValue,Map};
+use std::ops::{Index,IndexMut};
+
+///returned by Index for missing entries
+static NULL:DocValue = DocValue::Null;
+
+///
+/// doc["sports"]["match"]["scores"][1] reads by reference, a missing key,
+/// a index out of bounds or indexing a value of the wrong type gives null.
+///
+impl Index<&str> for DocValue{
+    type Output = DocValue;
+    fn index(&self,key:&str)->&DocValue{
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+impl Index<&String> for DocValue{
+    type Output = DocValue;
+    fn index(&self,key:&String)->&DocValue{
+        &self[key.as_str()]
+    }
+}
+
+impl Index<usize> for DocValue{
+    type Output = DocValue;
+    fn index(&self,index:usize)->&DocValue{
+        self.get_index(index).unwrap_or(&NULL)
+    }
+}
+
+///
+/// doc["sports"]["game"] = doc!("cricket") inserts missing keys as
+/// null and turns a null value into a empty object first, so nested
+/// objects are created on the way. panics if self is neither a object nor null.
+///
+impl IndexMut<&str> for DocValue{
+    fn index_mut(&mut self,key:&str)->&mut DocValue{
+        if let DocValue::Null = self{
+            *self = DocValue::Object(Map::new());
+        }
+        match self{
+            DocValue::Object(map)=>{
+                if !map.contains_key(key){
+                    map.insert(key.to_string(),DocValue::Null);
+                }
+                map.get_mut(key).unwrap()
+            },
+            _=>{panic!("cannot index a {} with key {:?}",self.type_name(),key)}
+        }
+    }
+}
+
+impl IndexMut<&String> for DocValue{
+    fn index_mut(&mut self,key:&String)->&mut DocValue{
+        &mut self[key.as_str()]
+    }
+}
+
+///panics if self is not a vec or index is out of bounds
+impl IndexMut<usize> for DocValue{
+    fn index_mut(&mut self,index:usize)->&mut DocValue{
+        match self{
+            DocValue::Vec(pool)=>{
+                let len = pool.len();
+                match pool.get_mut(index){
+                    Some(v)=>{v},
+                    None=>{panic!("index {} out of bounds of vec with {} entries",index,len)}
+                }
+            },
+            _=>{panic!("cannot index a {} with index {}",self.type_name(),index)}
+        }
+    }
+}
@@ -0,0 +1,500 @@
+use crate::{DocValue,DecodeOptions,JsonOptions,BinaryFormat,JsonError,Limit};
+use crate::Map;
+
+// json conversion of DocValue.
+//
+// num is written as a json integer and float always with a fraction or
+// exponent (15 vs 15.0) so the two stay apart. the values json has no
+// syntax for are written as tagged objects with a single key:
+//
+//     binary              {"$binary":"AQID"}      base64 with padding
+//     nan and infinities  {"$float":"NaN"}        also "inf" and "-inf"
+//
+// object keys starting with '$' are written with one more '$' in front
+// ("$ref" as "$$ref") so they never read as a tag, from_json strips it again.
+//
+// object keys are written sorted so the output of a document is stable,
+// with the preserve_order feature they are written in insertion order.
+
+const TAG_BINARY:&str = "$binary";
+const TAG_FLOAT:&str = "$float";
+
+//writer
+
+pub fn to_json(doc:&DocValue,options:JsonOptions)->String{
+    let mut out = String::new();
+    write_value(&mut out,doc,options,0);
+    out
+}
+
+fn write_value(out:&mut String,doc:&DocValue,options:JsonOptions,depth:usize){
+    match doc{
+        DocValue::Object(map)=>{
+            write_object(out,map,options,depth);
+        },
+        DocValue::Vec(pool)=>{
+            if pool.is_empty(){
+                out.push_str("[]");
+                return;
+            }
+            out.push('[');
+            for (index,item) in pool.iter().enumerate(){
+                if index > 0{out.push(',');}
+                newline(out,options,depth + 1);
+                write_value(out,item,options,depth + 1);
+            }
+            close(out,']',options,depth);
+        },
+        DocValue::Num(v)=>{
+            out.push_str(&v.to_string());
+        },
+        DocValue::Float(v)=>{
+            if v.is_finite(){
+                //debug formatting always keeps a '.' or a exponent
+                out.push_str(&format!("{:?}",v));
+            } else {
+                let name = if v.is_nan(){"NaN"} else if *v > 0.0 {"inf"} else {"-inf"};
+                write_tagged(out,TAG_FLOAT,name,options,depth);
+            }
+        },
+        DocValue::String(v)=>{
+            write_string(out,v);
+        },
+        DocValue::Binary(v)=>{
+            match options.binary{
+                BinaryFormat::Tagged=>{write_tagged(out,TAG_BINARY,&base64_encode(v),options,depth);},
+                BinaryFormat::Base64=>{write_string(out,&base64_encode(v));}
+            }
+        },
+        DocValue::Null=>{
+            out.push_str("null");
+        },
+        DocValue::Bool(v)=>{
+            out.push_str(if *v {"true"} else {"false"});
+        },
+    }
+}
+
+fn write_object(out:&mut String,map:&Map<String,DocValue>,options:JsonOptions,depth:usize){
+    if map.is_empty(){
+        out.push_str("{}");
+        return;
+    }
+    #[cfg(not(feature = "preserve_order"))]
+    let keys = {
+        let mut keys:Vec<&String> = map.keys().collect();
+        keys.sort();
+        keys
+    };
+    #[cfg(feature = "preserve_order")]
+    let keys:Vec<&String> = map.keys().collect();
+    out.push('{');
+    for (index,key) in keys.into_iter().enumerate(){
+        if index > 0{out.push(',');}
+        newline(out,options,depth + 1);
+        if key.starts_with('$'){
+            write_string(out,&format!("${}",key));
+        } else {
+            write_string(out,key);
+        }
+        out.push(':');
+        if options.pretty{out.push(' ');}
+        write_value(out,&map[key],options,depth + 1);
+    }
+    close(out,'}',options,depth);
+}
+
+fn write_tagged(out:&mut String,tag:&str,value:&str,options:JsonOptions,depth:usize){
+    open(out,'{',options,depth + 1);
+    write_string(out,tag);
+    out.push(':');
+    if options.pretty{out.push(' ');}
+    write_string(out,value);
+    close(out,'}',options,depth);
+}
+
+fn open(out:&mut String,c:char,options:JsonOptions,depth:usize){
+    out.push(c);
+    newline(out,options,depth);
+}
+
+fn close(out:&mut String,c:char,options:JsonOptions,depth:usize){
+    newline(out,options,depth);
+    out.push(c);
+}
+
+fn newline(out:&mut String,options:JsonOptions,depth:usize){
+    if options.pretty{
+        out.push('\n');
+        for _ in 0..depth{
+            out.push_str("  ");
+        }
+    }
+}
+
+fn write_string(out:&mut String,v:&str){
+    out.push('"');
+    for c in v.chars(){
+        match c{
+            '"'=>{out.push_str("\\\"");},
+            '\\'=>{out.push_str("\\\\");},
+            '\n'=>{out.push_str("\\n");},
+            '\r'=>{out.push_str("\\r");},
+            '\t'=>{out.push_str("\\t");},
+            '\u{8}'=>{out.push_str("\\b");},
+            '\u{c}'=>{out.push_str("\\f");},
+            c if (c as u32) < 0x20=>{out.push_str(&format!("\\u{:04x}",c as u32));},
+            c=>{out.push(c);}
+        }
+    }
+    out.push('"');
+}
+
+//reader
+
+pub fn from_json(text:&str,options:DecodeOptions)->Result<DocValue,JsonError>{
+    let mut parser = Parser{data:text.as_bytes(),pos:0,depth:0,options};
+    let value = parser.value()?;
+    parser.whitespace();
+    if parser.pos < parser.data.len(){
+        return Err(JsonError::TrailingCharacters{offset:parser.pos});
+    }
+    Ok(value)
+}
+
+struct Parser<'a>{
+    data:&'a [u8],
+    pos:usize,
+    depth:usize,
+    options:DecodeOptions,
+}
+
+impl<'a> Parser<'a>{
+
+    fn whitespace(&mut self){
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.data.get(self.pos){
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self)->Option<u8>{
+        self.whitespace();
+        self.data.get(self.pos).copied()
+    }
+
+    fn expect(&mut self,c:u8,expected:&'static str)->Result<(),JsonError>{
+        if self.peek() != Some(c){
+            return Err(JsonError::Syntax{expected,offset:self.pos});
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self,word:&str,value:DocValue)->Result<DocValue,JsonError>{
+        if !self.data[self.pos..].starts_with(word.as_bytes()){
+            return Err(JsonError::Syntax{expected:"value",offset:self.pos});
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn value(&mut self)->Result<DocValue,JsonError>{
+        match self.peek(){
+            Some(b'{')=>{self.object()},
+            Some(b'[')=>{self.vec()},
+            Some(b'"')=>{Ok(DocValue::String(self.string()?))},
+            Some(b't')=>{self.literal("true",DocValue::Bool(true))},
+            Some(b'f')=>{self.literal("false",DocValue::Bool(false))},
+            Some(b'n')=>{self.literal("null",DocValue::Null)},
+            Some(b'-' | b'0'..=b'9')=>{self.number()},
+            _=>{Err(JsonError::Syntax{expected:"value",offset:self.pos})}
+        }
+    }
+
+    fn enter(&mut self)->Result<(),JsonError>{
+        self.depth += 1;
+        if self.depth > self.options.max_depth{
+            return Err(JsonError::LimitExceeded{limit:Limit::Depth,offset:self.pos});
+        }
+        Ok(())
+    }
+
+    fn object(&mut self)->Result<DocValue,JsonError>{
+        let start = self.pos;
+        self.enter()?;
+        self.pos += 1;
+        let mut map = Map::new();
+        //a object is a tag if all its keys are written as a tag
+        let mut tag = true;
+        if self.peek() == Some(b'}'){
+            self.pos += 1;
+        } else {
+            loop{
+                if self.peek() != Some(b'"'){
+                    return Err(JsonError::Syntax{expected:"object key",offset:self.pos});
+                }
+                let mut key = self.string()?;
+                if key.starts_with("$$"){
+                    key.remove(0);
+                    tag = false;
+                } else if key != TAG_BINARY && key != TAG_FLOAT{
+                    tag = false;
+                }
+                self.expect(b':',"':'")?;
+                let value = self.value()?;
+                map.insert(key,value);
+                if map.len() > self.options.max_entries{
+                    return Err(JsonError::LimitExceeded{limit:Limit::Entries,offset:self.pos});
+                }
+                match self.peek(){
+                    Some(b',')=>{self.pos += 1;},
+                    Some(b'}')=>{self.pos += 1;break;},
+                    _=>{return Err(JsonError::Syntax{expected:"',' or '}'",offset:self.pos});}
+                }
+            }
+        }
+        self.depth -= 1;
+        if tag && map.len() == 1{
+            return untag(map,start);
+        }
+        Ok(DocValue::Object(map))
+    }
+
+    fn vec(&mut self)->Result<DocValue,JsonError>{
+        self.enter()?;
+        self.pos += 1;
+        let mut pool = Vec::new();
+        if self.peek() == Some(b']'){
+            self.pos += 1;
+        } else {
+            loop{
+                pool.push(self.value()?);
+                if pool.len() > self.options.max_entries{
+                    return Err(JsonError::LimitExceeded{limit:Limit::Entries,offset:self.pos});
+                }
+                match self.peek(){
+                    Some(b',')=>{self.pos += 1;},
+                    Some(b']')=>{self.pos += 1;break;},
+                    _=>{return Err(JsonError::Syntax{expected:"',' or ']'",offset:self.pos});}
+                }
+            }
+        }
+        self.depth -= 1;
+        Ok(DocValue::Vec(pool))
+    }
+
+    fn number(&mut self)->Result<DocValue,JsonError>{
+        let start = self.pos;
+        let digits = |p:&mut Parser|->usize{
+            let from = p.pos;
+            while let Some(b'0'..=b'9') = p.data.get(p.pos){
+                p.pos += 1;
+            }
+            p.pos - from
+        };
+        if self.data[self.pos] == b'-'{
+            self.pos += 1;
+        }
+        let int_start = self.pos;
+        let int_len = digits(self);
+        if int_len == 0 || (int_len > 1 && self.data[int_start] == b'0'){
+            return Err(JsonError::InvalidNumber{offset:start});
+        }
+        let mut float = false;
+        if self.data.get(self.pos) == Some(&b'.'){
+            self.pos += 1;
+            float = true;
+            if digits(self) == 0{
+                return Err(JsonError::InvalidNumber{offset:start});
+            }
+        }
+        if let Some(b'e' | b'E') = self.data.get(self.pos){
+            self.pos += 1;
+            float = true;
+            if let Some(b'+' | b'-') = self.data.get(self.pos){
+                self.pos += 1;
+            }
+            if digits(self) == 0{
+                return Err(JsonError::InvalidNumber{offset:start});
+            }
+        }
+        //ascii digits and signs only, always valid utf8
+        let text = std::str::from_utf8(&self.data[start..self.pos]).unwrap();
+        if !float{
+            //integers outside i64 fall back to a float
+            if let Ok(v) = text.parse::<i64>(){
+                return Ok(DocValue::Num(v));
+            }
+        }
+        match text.parse::<f64>(){
+            Ok(v)=>{Ok(DocValue::Float(v))},
+            Err(_)=>{Err(JsonError::InvalidNumber{offset:start})}
+        }
+    }
+
+    fn string(&mut self)->Result<String,JsonError>{
+        let start = self.pos;
+        self.pos += 1;
+        let mut build = Vec::new();
+        loop{
+            let c = match self.data.get(self.pos){
+                Some(c)=>{*c},
+                None=>{return Err(JsonError::Syntax{expected:"'\"'",offset:self.pos});}
+            };
+            match c{
+                b'"'=>{
+                    self.pos += 1;
+                    break;
+                },
+                b'\\'=>{
+                    let escape = self.pos;
+                    self.pos += 1;
+                    let c = match self.data.get(self.pos){
+                        Some(c)=>{*c},
+                        None=>{return Err(JsonError::InvalidEscape{offset:escape});}
+                    };
+                    self.pos += 1;
+                    match c{
+                        b'"'=>{build.push(b'"');},
+                        b'\\'=>{build.push(b'\\');},
+                        b'/'=>{build.push(b'/');},
+                        b'b'=>{build.push(8);},
+                        b'f'=>{build.push(12);},
+                        b'n'=>{build.push(b'\n');},
+                        b'r'=>{build.push(b'\r');},
+                        b't'=>{build.push(b'\t');},
+                        b'u'=>{
+                            let mut code = self.hex4(escape)?;
+                            if (0xd800..0xdc00).contains(&code){
+                                if !self.data[self.pos..].starts_with(b"\\u"){
+                                    return Err(JsonError::InvalidEscape{offset:escape});
+                                }
+                                self.pos += 2;
+                                let low = self.hex4(escape)?;
+                                if !(0xdc00..0xe000).contains(&low){
+                                    return Err(JsonError::InvalidEscape{offset:escape});
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            match char::from_u32(code){
+                                Some(c)=>{
+                                    let mut buf = [0;4];
+                                    build.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                                },
+                                None=>{return Err(JsonError::InvalidEscape{offset:escape});}
+                            }
+                        },
+                        _=>{return Err(JsonError::InvalidEscape{offset:escape});}
+                    }
+                },
+                c if c < 0x20=>{
+                    return Err(JsonError::Syntax{expected:"escaped control character",offset:self.pos});
+                },
+                c=>{
+                    build.push(c);
+                    self.pos += 1;
+                }
+            }
+            if build.len() > self.options.max_len{
+                return Err(JsonError::LimitExceeded{limit:Limit::Length,offset:start});
+            }
+        }
+        //input is a str and escapes push whole chars
+        Ok(String::from_utf8(build).unwrap())
+    }
+
+    fn hex4(&mut self,escape:usize)->Result<u32,JsonError>{
+        let hex = match self.data.get(self.pos..self.pos + 4){
+            Some(hex)=>{hex},
+            None=>{return Err(JsonError::InvalidEscape{offset:escape});}
+        };
+        let mut code = 0;
+        for c in hex{
+            let digit = match (*c as char).to_digit(16){
+                Some(d)=>{d},
+                None=>{return Err(JsonError::InvalidEscape{offset:escape});}
+            };
+            code = code * 16 + digit;
+        }
+        self.pos += 4;
+        Ok(code)
+    }
+
+}
+
+///turns a object holding a single tag back into the value it stands for
+fn untag(mut map:Map<String,DocValue>,offset:usize)->Result<DocValue,JsonError>{
+    if let Some(value) = map.remove(TAG_BINARY){
+        return match value{
+            DocValue::String(v)=>{
+                match base64_decode(v.as_bytes()){
+                    Some(v)=>{Ok(DocValue::Binary(v))},
+                    None=>{Err(JsonError::InvalidTag{offset})}
+                }
+            },
+            _=>{Err(JsonError::InvalidTag{offset})}
+        };
+    }
+    match map.remove(TAG_FLOAT){
+        Some(DocValue::String(v))=>{
+            match v.as_str(){
+                "NaN"=>{Ok(DocValue::Float(f64::NAN))},
+                "inf"=>{Ok(DocValue::Float(f64::INFINITY))},
+                "-inf"=>{Ok(DocValue::Float(f64::NEG_INFINITY))},
+                _=>{Err(JsonError::InvalidTag{offset})}
+            }
+        },
+        _=>{Err(JsonError::InvalidTag{offset})}
+    }
+}
+
+//base64, standard alphabet with padding
+
+const BASE64:&[u8;64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data:&[u8])->String{
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3){
+        let b = [chunk[0],*chunk.get(1).unwrap_or(&0),*chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4{
+            if i <= chunk.len(){
+                out.push(BASE64[(n >> (18 - i * 6)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn base64_decode(data:&[u8])->Option<Vec<u8>>{
+    if !data.len().is_multiple_of(4){
+        return None;
+    }
+    let mut out = Vec::with_capacity(data.len() / 4 * 3);
+    for (index,chunk) in data.chunks(4).enumerate(){
+        let last = index + 1 == data.len() / 4;
+        let mut n = 0u32;
+        let mut pad = 0;
+        for (i,c) in chunk.iter().enumerate(){
+            let v = match c{
+                b'A'..=b'Z'=>{c - b'A'},
+                b'a'..=b'z'=>{c - b'a' + 26},
+                b'0'..=b'9'=>{c - b'0' + 52},
+                b'+'=>{62},
+                b'/'=>{63},
+                b'=' if last && i >= 2=>{pad += 1;0},
+                _=>{return None;}
+            };
+            //nothing but padding after the first '='
+            if pad > 0 && *c != b'='{
+                return None;
+            }
+            n = (n << 6) | u32::from(v);
+        }
+        let bytes = [(n >> 16) as u8,(n >> 8) as u8,n as u8];
+        out.extend_from_slice(&bytes[..3 - pad]);
+    }
+    Some(out)
+}
@@ -0,0 +1,91 @@
+use crate::{DocValue,DocRef,DecodeError,DecodeOptions};
+use crate::reader::{Reader,find_path,entry_count,read_data_line,read_len};
+use crate::header::{Header,FLAG_COUNTED};
+use std::ops::Range;
+
+///
+/// decodes only the value at path from a encoded document, unrelated
+/// values are skipped using their length prefixes. vec entries are
+/// addressed with their index as a string.
+///
+/// let game = gzbbinarydoc::get_path(&bin,&["sports","game"]);
+/// let score = gzbbinarydoc::get_path(&bin,&["sports","match","scores","1"]);
+///
+pub fn get_path(data:&[u8],path:&[&str])->Result<Option<DocValue>,DecodeError>{
+    #[cfg(feature = "compression")]
+    if let Some(data) = crate::compress::decompress(data,DecodeOptions::default())?{
+        return get_path(&data,path);
+    }
+    let mut global = Reader::new(data,DecodeOptions::default());
+    let mut frame = match find_path(&mut global,path)?{
+        Some(v)=>{v},
+        None=>{return Ok(None);}
+    };
+    let value:DocRef = read_data_line(&mut global,&mut frame)?;
+    Ok(Some(value.to_owned()))
+}
+
+///
+/// byte range of the encoded value at path, the range holds a complete
+/// data line without header. only for documents written by DocValue::write
+/// (format v1 without header) it can be decoded on its own, every other
+/// document needs its header in front, get_path_bytes builds that document.
+/// compressed documents have no such range and return DecodeError::Compressed.
+///
+pub fn get_path_range(data:&[u8],path:&[&str])->Result<Option<Range<usize>>,DecodeError>{
+    let mut global = Reader::new(data,DecodeOptions::default());
+    let mut frame = match find_path(&mut global,path)?{
+        Some(v)=>{v},
+        None=>{return Ok(None);}
+    };
+    let start = frame.offset();
+    frame.read(&mut global,1)?;
+    let data_len = read_len(&mut global,&mut frame)?;
+    frame.sub(&mut global,data_len)?;
+    Ok(Some(start..frame.offset()))
+}
+
+///
+/// encoded value at path as a document of its own that DocValue::read and
+/// DocRef::read decode. the data line of get_path_range follows a header
+/// with the version and counted flag of data, a document without header
+/// gives the bare data line. compressed documents are decompressed first.
+///
+/// let game_match = gzbbinarydoc::get_path_bytes(&bin,&["sports","match"]);
+///
+pub fn get_path_bytes(data:&[u8],path:&[&str])->Result<Option<Vec<u8>>,DecodeError>{
+    #[cfg(feature = "compression")]
+    if let Some(data) = crate::compress::decompress(data,DecodeOptions::default())?{
+        return get_path_bytes(&data,path);
+    }
+    let range = match get_path_range(data,path)?{
+        Some(v)=>{v},
+        None=>{return Ok(None);}
+    };
+    let mut build = vec![];
+    if let Some(header) = Header::parse(data)?{
+        build.extend_from_slice(&Header::new(header.version,header.flags & FLAG_COUNTED).to_bytes());
+    }
+    build.extend_from_slice(&data[range]);
+    Ok(Some(build))
+}
+
+///
+/// number of entries of the object or vec at path without decoding them,
+/// documents written with EncodeOptions::counted store it up front. none if
+/// the path does not exist or does not point to a object or vec.
+///
+/// let games = gzbbinarydoc::get_path_len(&bin,&["sports","match","scores"]);
+///
+pub fn get_path_len(data:&[u8],path:&[&str])->Result<Option<usize>,DecodeError>{
+    #[cfg(feature = "compression")]
+    if let Some(data) = crate::compress::decompress(data,DecodeOptions::default())?{
+        return get_path_len(&data,path);
+    }
+    let mut global = Reader::new(data,DecodeOptions::default());
+    let mut frame = match find_path(&mut global,path)?{
+        Some(v)=>{v},
+        None=>{return Ok(None);}
+    };
+    entry_count(&mut global,&mut frame)
+}
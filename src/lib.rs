@@ -1,14 +1,32 @@
-
-
+#[macro_use]
+mod macros;
 mod reader;
 mod writer;
 mod doc;
+mod cmp;
+mod index;
+mod pointer;
+mod doc_ref;
+mod error;
+mod options;
+mod lazy;
+mod header;
+mod checksum;
+mod from_doc;
+mod map;
+mod json;
+#[cfg(feature = "compression")]
+mod compress;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(feature = "serde")]
+mod de;
 
 ///
 /// let mut person = DocValue::object();
 /// 
 /// person.insert("name","akku");
-/// person.insert("bin",vec![1,2,3]);
+/// person.insert("bin",vec![1u8,2,3]);
 /// person.insert("age","five");
 /// person.insert("score",12.65);
 /// person.insert(&"king",true);
@@ -22,4 +40,22 @@ mod doc;
 /// 
 /// let mut game_match = DocValue::object();
 /// game_match.insert("scores",scores);
-pub use doc::DocValue;
\ No newline at end of file
+pub use doc::{DocValue,ToDocValue,TryToDocValue,ToDocError};
+pub use map::{Map,MapIntoIter,MapIter,MapIterMut,MapKeys,MapValues,MapValuesMut};
+pub use from_doc::{FromDocValue,FromDocError};
+#[cfg(feature = "derive")]
+pub use gzbbinarydoc_derive::{ToDocValue,FromDocValue};
+pub use doc_ref::DocRef;
+pub use error::{DecodeError,JsonError,Limit,PointerError};
+pub use options::{BinaryFormat,DecodeOptions,EncodeOptions,Format,JsonOptions};
+pub use lazy::{get_path,get_path_bytes,get_path_len,get_path_range};
+pub use checksum::verify;
+pub use reader::is_canonical;
+#[cfg(feature = "compression")]
+pub use compress::COMPRESSION_THRESHOLD;
+#[cfg(feature = "serde")]
+pub use error::SerdeError;
+#[cfg(feature = "serde")]
+pub use ser::to_bytes;
+#[cfg(feature = "serde")]
+pub use de::{from_bytes,from_bytes_with,from_bytes_owned,from_bytes_owned_with};
\ No newline at end of file
@@ -0,0 +1,89 @@
+///
+/// builds a DocValue from literal syntax.
+///
+/// let person = doc!({
+///     "name":"akku",
+///     "bin":bin[1,2,3],
+///     "network":null,
+///     "sports":{
+///         "game":"cricket",
+///         "match":{"scores":[12.5,15,null,false]}
+///     }
+/// });
+///
+/// {..} is a object and [..] a vec, keys are string literals or any tt
+/// whose to_string gives the key. bin[..] is binary from u8 values and
+/// null is null, every other value is a expression passed through ToDocValue.
+///
+#[macro_export]
+macro_rules! doc{
+
+    //vec entries
+
+    (@vec [$($done:expr,)*])=>{
+        vec![$($done,)*]
+    };
+    (@vec [$($done:expr,)*] null $(,$($rest:tt)*)?)=>{
+        $crate::doc!(@vec [$($done,)* $crate::DocValue::Null,] $($($rest)*)?)
+    };
+    (@vec [$($done:expr,)*] bin[$($byte:tt)*] $(,$($rest:tt)*)?)=>{
+        $crate::doc!(@vec [$($done,)* $crate::doc!(bin[$($byte)*]),] $($($rest)*)?)
+    };
+    (@vec [$($done:expr,)*] [$($pool:tt)*] $(,$($rest:tt)*)?)=>{
+        $crate::doc!(@vec [$($done,)* $crate::doc!([$($pool)*]),] $($($rest)*)?)
+    };
+    (@vec [$($done:expr,)*] {$($map:tt)*} $(,$($rest:tt)*)?)=>{
+        $crate::doc!(@vec [$($done,)* $crate::doc!({$($map)*}),] $($($rest)*)?)
+    };
+    (@vec [$($done:expr,)*] $value:expr $(,$($rest:tt)*)?)=>{
+        $crate::doc!(@vec [$($done,)* $crate::doc!($value),] $($($rest)*)?)
+    };
+
+    //object entries
+
+    (@object $map:ident)=>{};
+    (@object $map:ident $key:tt : null $(,$($rest:tt)*)?)=>{
+        $map.insert(::std::string::ToString::to_string(&$key),$crate::DocValue::Null);
+        $crate::doc!(@object $map $($($rest)*)?);
+    };
+    (@object $map:ident $key:tt : bin[$($byte:tt)*] $(,$($rest:tt)*)?)=>{
+        $map.insert(::std::string::ToString::to_string(&$key),$crate::doc!(bin[$($byte)*]));
+        $crate::doc!(@object $map $($($rest)*)?);
+    };
+    (@object $map:ident $key:tt : [$($pool:tt)*] $(,$($rest:tt)*)?)=>{
+        $map.insert(::std::string::ToString::to_string(&$key),$crate::doc!([$($pool)*]));
+        $crate::doc!(@object $map $($($rest)*)?);
+    };
+    (@object $map:ident $key:tt : {$($inner:tt)*} $(,$($rest:tt)*)?)=>{
+        $map.insert(::std::string::ToString::to_string(&$key),$crate::doc!({$($inner)*}));
+        $crate::doc!(@object $map $($($rest)*)?);
+    };
+    (@object $map:ident $key:tt : $value:expr $(,$($rest:tt)*)?)=>{
+        $map.insert(::std::string::ToString::to_string(&$key),$crate::doc!($value));
+        $crate::doc!(@object $map $($($rest)*)?);
+    };
+
+    //values
+
+    (null)=>{
+        $crate::DocValue::Null
+    };
+    (bin[$($byte:expr),* $(,)?])=>{
+        $crate::DocValue::Binary(::std::vec::Vec::<u8>::from([$($byte),*]))
+    };
+    ([$($pool:tt)*])=>{
+        $crate::DocValue::Vec($crate::doc!(@vec [] $($pool)*))
+    };
+    ({$($map:tt)*})=>{
+        {
+            #[allow(unused_mut)]
+            let mut map = $crate::Map::new();
+            $crate::doc!(@object map $($map)*);
+            $crate::DocValue::Object(map)
+        }
+    };
+    ($value:expr)=>{
+        $crate::ToDocValue::toDocValue(&$value)
+    };
+
+}
@@ -0,0 +1,219 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FusedIterator;
+use std::ops::{Index,IndexMut};
+
+#[cfg(not(feature = "preserve_order"))]
+type Inner<K,V> = std::collections::HashMap<K,V>;
+#[cfg(feature = "preserve_order")]
+type Inner<K,V> = indexmap::IndexMap<K,V>;
+
+///
+/// map behind DocValue::Object and DocRef::Object. its api is the same with
+/// and without the preserve_order feature, only the private store changes:
+/// a HashMap by default, a IndexMap that keeps keys in the order they were
+/// inserted, written and read with the feature.
+///
+pub struct Map<K = String,V = crate::DocValue>{
+    inner:Inner<K,V>,
+}
+
+impl<K,V> Map<K,V>{
+    pub fn new()->Map<K,V>{
+        Map{inner:Inner::default()}
+    }
+    pub fn with_capacity(capacity:usize)->Map<K,V>{
+        Map{inner:Inner::with_capacity_and_hasher(capacity,Default::default())}
+    }
+    pub fn len(&self)->usize{
+        self.inner.len()
+    }
+    pub fn is_empty(&self)->bool{
+        self.inner.is_empty()
+    }
+    pub fn clear(&mut self){
+        self.inner.clear();
+    }
+    ///entries in insertion order with preserve_order, otherwise in no particular order
+    pub fn iter(&self)->MapIter<'_,K,V>{
+        MapIter{inner:self.inner.iter()}
+    }
+    pub fn iter_mut(&mut self)->MapIterMut<'_,K,V>{
+        MapIterMut{inner:self.inner.iter_mut()}
+    }
+    pub fn keys(&self)->MapKeys<'_,K,V>{
+        MapKeys{inner:self.inner.keys()}
+    }
+    pub fn values(&self)->MapValues<'_,K,V>{
+        MapValues{inner:self.inner.values()}
+    }
+    pub fn values_mut(&mut self)->MapValuesMut<'_,K,V>{
+        MapValuesMut{inner:self.inner.values_mut()}
+    }
+}
+
+impl<K:Hash + Eq,V> Map<K,V>{
+    pub fn get<Q>(&self,key:&Q)->Option<&V> where K:Borrow<Q>,Q:Hash + Eq + ?Sized{
+        self.inner.get(key)
+    }
+    pub fn get_mut<Q>(&mut self,key:&Q)->Option<&mut V> where K:Borrow<Q>,Q:Hash + Eq + ?Sized{
+        self.inner.get_mut(key)
+    }
+    pub fn get_key_value<Q>(&self,key:&Q)->Option<(&K,&V)> where K:Borrow<Q>,Q:Hash + Eq + ?Sized{
+        self.inner.get_key_value(key)
+    }
+    pub fn contains_key<Q>(&self,key:&Q)->bool where K:Borrow<Q>,Q:Hash + Eq + ?Sized{
+        self.inner.contains_key(key)
+    }
+    ///returns the old value of key, a existing key keeps its position
+    pub fn insert(&mut self,key:K,value:V)->Option<V>{
+        self.inner.insert(key,value)
+    }
+    ///removes key, keeping the order of the other keys with preserve_order
+    pub fn remove<Q>(&mut self,key:&Q)->Option<V> where K:Borrow<Q>,Q:Hash + Eq + ?Sized{
+        self.remove_entry(key).map(|(_,v)|v)
+    }
+    pub fn remove_entry<Q>(&mut self,key:&Q)->Option<(K,V)> where K:Borrow<Q>,Q:Hash + Eq + ?Sized{
+        #[cfg(feature = "preserve_order")]
+        return self.inner.shift_remove_entry(key);
+        #[cfg(not(feature = "preserve_order"))]
+        return self.inner.remove_entry(key);
+    }
+    ///keeps the entries f returns true for
+    pub fn retain<F:FnMut(&K,&mut V)->bool>(&mut self,f:F){
+        self.inner.retain(f);
+    }
+}
+
+impl<K,V> Default for Map<K,V>{
+    fn default()->Map<K,V>{
+        Map::new()
+    }
+}
+
+impl<K:Clone,V:Clone> Clone for Map<K,V>{
+    fn clone(&self)->Map<K,V>{
+        Map{inner:self.inner.clone()}
+    }
+}
+
+impl<K:fmt::Debug,V:fmt::Debug> fmt::Debug for Map<K,V>{
+    fn fmt(&self,f:&mut fmt::Formatter<'_>)->fmt::Result{
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+///equal if both hold the same entries, the order never matters
+impl<K:Hash + Eq,V:PartialEq> PartialEq for Map<K,V>{
+    fn eq(&self,other:&Map<K,V>)->bool{
+        self.inner == other.inner
+    }
+}
+
+impl<K:Hash + Eq,V:Eq> Eq for Map<K,V>{}
+
+///panics if key is missing like HashMap
+impl<K,V,Q> Index<&Q> for Map<K,V> where K:Borrow<Q> + Hash + Eq,Q:Hash + Eq + ?Sized{
+    type Output = V;
+    fn index(&self,key:&Q)->&V{
+        self.get(key).expect("key not found in Map")
+    }
+}
+
+impl<K,V,Q> IndexMut<&Q> for Map<K,V> where K:Borrow<Q> + Hash + Eq,Q:Hash + Eq + ?Sized{
+    fn index_mut(&mut self,key:&Q)->&mut V{
+        self.get_mut(key).expect("key not found in Map")
+    }
+}
+
+impl<K:Hash + Eq,V> FromIterator<(K,V)> for Map<K,V>{
+    fn from_iter<I:IntoIterator<Item = (K,V)>>(iter:I)->Map<K,V>{
+        Map{inner:Inner::from_iter(iter)}
+    }
+}
+
+impl<K:Hash + Eq,V> Extend<(K,V)> for Map<K,V>{
+    fn extend<I:IntoIterator<Item = (K,V)>>(&mut self,iter:I){
+        self.inner.extend(iter);
+    }
+}
+
+impl<K,V> IntoIterator for Map<K,V>{
+    type Item = (K,V);
+    type IntoIter = MapIntoIter<K,V>;
+    fn into_iter(self)->MapIntoIter<K,V>{
+        MapIntoIter{inner:self.inner.into_iter()}
+    }
+}
+
+impl<'a,K,V> IntoIterator for &'a Map<K,V>{
+    type Item = (&'a K,&'a V);
+    type IntoIter = MapIter<'a,K,V>;
+    fn into_iter(self)->MapIter<'a,K,V>{
+        self.iter()
+    }
+}
+
+impl<'a,K,V> IntoIterator for &'a mut Map<K,V>{
+    type Item = (&'a K,&'a mut V);
+    type IntoIter = MapIterMut<'a,K,V>;
+    fn into_iter(self)->MapIterMut<'a,K,V>{
+        self.iter_mut()
+    }
+}
+
+///iterator wrapping the one of the private store
+macro_rules! map_iter{
+    ($(#[$doc:meta])* $name:ident<$($lt:lifetime,)?K,V>=>$inner:ty,$item:ty)=>{
+        $(#[$doc])*
+        pub struct $name<$($lt,)?K = String,V = crate::DocValue>{
+            inner:$inner,
+        }
+        impl<$($lt,)?K,V> Iterator for $name<$($lt,)?K,V>{
+            type Item = $item;
+            fn next(&mut self)->Option<$item>{
+                self.inner.next()
+            }
+            fn size_hint(&self)->(usize,Option<usize>){
+                self.inner.size_hint()
+            }
+        }
+        impl<$($lt,)?K,V> ExactSizeIterator for $name<$($lt,)?K,V>{
+            fn len(&self)->usize{
+                self.inner.len()
+            }
+        }
+        impl<$($lt,)?K,V> FusedIterator for $name<$($lt,)?K,V>{}
+    };
+}
+
+#[cfg(not(feature = "preserve_order"))]
+use std::collections::hash_map as store;
+#[cfg(feature = "preserve_order")]
+use indexmap::map as store;
+
+map_iter!(
+    ///iterator of Map::iter
+    MapIter<'a,K,V>=>store::Iter<'a,K,V>,(&'a K,&'a V)
+);
+map_iter!(
+    ///iterator of Map::iter_mut
+    MapIterMut<'a,K,V>=>store::IterMut<'a,K,V>,(&'a K,&'a mut V)
+);
+map_iter!(
+    ///iterator of Map::into_iter
+    MapIntoIter<K,V>=>store::IntoIter<K,V>,(K,V)
+);
+map_iter!(
+    ///iterator of Map::keys
+    MapKeys<'a,K,V>=>store::Keys<'a,K,V>,&'a K
+);
+map_iter!(
+    ///iterator of Map::values
+    MapValues<'a,K,V>=>store::Values<'a,K,V>,&'a V
+);
+map_iter!(
+    ///iterator of Map::values_mut
+    MapValuesMut<'a,K,V>=>store::ValuesMut<'a,K,V>,&'a mut V
+);
@@ -0,0 +1,126 @@
+///
+/// limits applied while decoding, a limit that is hit makes the reader
+/// return DecodeError::LimitExceeded instead of recursing or allocating
+/// further. DocValue::read uses DecodeOptions::default().
+///
+/// let options = DecodeOptions{max_depth:16,..DecodeOptions::default()};
+/// let doc = DocValue::read_with(&bin,options);
+///
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub struct DecodeOptions{
+    /// maximum nesting of objects and vecs, the root container is depth 1
+    pub max_depth:usize,
+    /// maximum length in bytes of a single string, binary or object key
+    pub max_len:usize,
+    /// maximum number of entries in a single object or vec
+    pub max_entries:usize,
+    /// maximum number of bytes read from the input for the whole document
+    pub max_total_bytes:usize,
+}
+
+impl DecodeOptions{
+    pub fn new()->DecodeOptions{
+        DecodeOptions::default()
+    }
+}
+
+impl Default for DecodeOptions{
+    fn default()->DecodeOptions{
+        DecodeOptions{
+            max_depth:128,
+            max_len:256 * 1024 * 1024,
+            max_entries:16 * 1024 * 1024,
+            max_total_bytes:1024 * 1024 * 1024,
+        }
+    }
+}
+
+///
+/// wire format written by DocValue::write_with, the reader detects the
+/// format from the header so documents of every format are read by read.
+///
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Default)]
+pub enum Format{
+    /// lengths are fixed 8 byte big endian u64, written without header by default
+    #[default]
+    V1,
+    /// lengths are LEB128 varints, always written with a header
+    V2,
+}
+
+impl Format{
+    ///version number stored in the header
+    pub fn version(&self)->u8{
+        match self{
+            Format::V1=>{1},
+            Format::V2=>{2},
+        }
+    }
+}
+
+///
+/// options for DocValue::write_with and DocValue::write_to_with, the
+/// default writes the same bytes as DocValue::write.
+///
+/// let options = EncodeOptions{format:Format::V2,..EncodeOptions::default()};
+/// let bin = person.write_with(options);
+///
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Default)]
+pub struct EncodeOptions{
+    /// wire format of lengths and entries
+    pub format:Format,
+    /// write the magic header, formats after V1 always have one
+    pub header:bool,
+    /// store the entry count in front of every object and vec instead of
+    /// a continue byte after every entry, implies a header
+    pub counted:bool,
+    /// append a crc32c of the document that read checks before decoding,
+    /// implies a header
+    pub checksum:bool,
+    /// store the document as a lz4 block if its encoding is at least this
+    /// many bytes, smaller documents are written uncompressed. ignored
+    /// without the compression feature so the field exists either way.
+    pub compression:Option<usize>,
+    /// write object keys sorted by their utf8 bytes, -0.0 as 0.0 and every
+    /// nan as the same quiet nan so equal documents give equal bytes
+    pub canonical:bool,
+}
+
+impl EncodeOptions{
+    pub fn new()->EncodeOptions{
+        EncodeOptions::default()
+    }
+}
+
+///
+/// how DocValue::to_json_with writes binary values
+///
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Default)]
+pub enum BinaryFormat{
+    /// tagged object {"$binary":"<base64>"}, read back as binary by from_json
+    #[default]
+    Tagged,
+    /// plain base64 string, shorter but read back as a string
+    Base64,
+}
+
+///
+/// options for DocValue::to_json_with, the default writes the same text
+/// as DocValue::to_json.
+///
+/// let options = JsonOptions{pretty:true,..JsonOptions::default()};
+/// let json = person.to_json_with(options);
+///
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Default)]
+pub struct JsonOptions{
+    /// representation of binary values
+    pub binary:BinaryFormat,
+    /// indent with two spaces and put every entry on its own line
+    pub pretty:bool,
+}
+
+impl JsonOptions{
+    pub fn new()->JsonOptions{
+        JsonOptions::default()
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{DocValue,ToDocValue,PointerError};
+
+///
+/// json pointer addressing (RFC 6901). a pointer is empty for the value
+/// itself or a '/' followed by tokens joined with '/', in a token "~1"
+/// stands for '/' and "~0" for '~'. a token is a object key, or a vec index
+/// written without leading zeros. pointer_set also takes "-" as the index
+/// past the last entry of a vec to push to it.
+///
+/// doc.pointer("/sports/match/scores/1")
+/// doc.pointer("/a~1b")        key "a/b"
+///
+impl DocValue{
+    ///value at pointer, None if the pointer is invalid or does not resolve
+    pub fn pointer(&self,pointer:&str)->Option<&DocValue>{
+        let mut hold = self;
+        for token in tokens(pointer)?{
+            hold = match hold{
+                DocValue::Object(map)=>{map.get(&token)?},
+                DocValue::Vec(pool)=>{pool.get(index_of(&token)?)?},
+                _=>{return None;}
+            };
+        }
+        Some(hold)
+    }
+    pub fn pointer_mut(&mut self,pointer:&str)->Option<&mut DocValue>{
+        let mut hold = self;
+        for token in tokens(pointer)?{
+            hold = match hold{
+                DocValue::Object(map)=>{map.get_mut(&token)?},
+                DocValue::Vec(pool)=>{pool.get_mut(index_of(&token)?)?},
+                _=>{return None;}
+            };
+        }
+        Some(hold)
+    }
+    ///
+    /// sets the value at pointer and returns the value it replaced. the
+    /// parent of the last token must exist, a object gets the key inserted
+    /// and a vec has the entry replaced or pushed if the index is its length or "-".
+    ///
+    pub fn pointer_set<T:ToDocValue>(&mut self,pointer:&str,value:T)->Result<Option<DocValue>,PointerError>{
+        let invalid = ||PointerError::Invalid{pointer:pointer.to_string()};
+        let mut tokens = tokens(pointer).ok_or_else(invalid)?;
+        let last = match tokens.pop(){
+            Some(last)=>{last},
+            None=>{return Ok(Some(std::mem::replace(self,value.toDocValue())));}
+        };
+        let parent_pointer = &pointer[..pointer.rfind('/').unwrap()];
+        let parent = match self.pointer_mut(parent_pointer){
+            Some(parent)=>{parent},
+            None=>{return Err(PointerError::NotFound{path:parent_pointer.to_string()});}
+        };
+        match parent{
+            DocValue::Object(map)=>{
+                Ok(map.insert(last,value.toDocValue()))
+            },
+            DocValue::Vec(pool)=>{
+                let index = match last.as_str(){
+                    "-"=>{pool.len()},
+                    _=>{index_of(&last).ok_or_else(||PointerError::InvalidIndex{path:pointer.to_string()})?}
+                };
+                if index == pool.len(){
+                    pool.push(value.toDocValue());
+                    return Ok(None);
+                }
+                match pool.get_mut(index){
+                    Some(hold)=>{Ok(Some(std::mem::replace(hold,value.toDocValue())))},
+                    None=>{Err(PointerError::InvalidIndex{path:pointer.to_string()})}
+                }
+            },
+            _=>{Err(PointerError::NotContainer{path:parent_pointer.to_string()})}
+        }
+    }
+    ///removes the value at pointer, vec entries after it move down by one
+    pub fn pointer_remove(&mut self,pointer:&str)->Option<DocValue>{
+        let mut tokens = tokens(pointer)?;
+        let last = tokens.pop()?;
+        let parent = self.pointer_mut(&pointer[..pointer.rfind('/').unwrap()])?;
+        match parent{
+            DocValue::Object(map)=>{map.remove(last.as_str())},
+            DocValue::Vec(pool)=>{
+                let index = index_of(&last)?;
+                if index < pool.len(){
+                    return Some(pool.remove(index));
+                }
+                None
+            },
+            _=>{None}
+        }
+    }
+}
+
+///unescaped tokens of pointer, None if it does not start with '/' or has a bad '~' escape
+fn tokens(pointer:&str)->Option<Vec<String>>{
+    if pointer.is_empty(){
+        return Some(vec![]);
+    }
+    let rest = pointer.strip_prefix('/')?;
+    let mut build = vec![];
+    for raw in rest.split('/'){
+        let mut token = String::with_capacity(raw.len());
+        let mut chars = raw.chars();
+        while let Some(c) = chars.next(){
+            if c != '~'{
+                token.push(c);
+                continue;
+            }
+            match chars.next(){
+                Some('0')=>{token.push('~');},
+                Some('1')=>{token.push('/');},
+                _=>{return None;}
+            }
+        }
+        build.push(token);
+    }
+    Some(build)
+}
+
+///vec index of a token, digits without leading zeros
+fn index_of(token:&str)->Option<usize>{
+    if token.is_empty() || !token.bytes().all(|b|b.is_ascii_digit()){
+        return None;
+    }
+    if token.len() > 1 && token.starts_with('0'){
+        return None;
+    }
+    token.parse().ok()
+}
@@ -1,13 +1,30 @@
-
-use crate::DocValue;
+use crate::{DocRef,DecodeError,DecodeOptions,Map};
+use crate::error::Limit;
+use crate::header::{Header,MAGIC,HEADER_LEN,FLAG_COUNTED,FLAG_CHECKSUM,FLAG_COMPRESSED};
+use crate::checksum::{self,CHECKSUM_LEN};
 use byteorder::{BigEndian, ReadBytesExt};
-use std::{io::Cursor, collections::HashMap};
+use std::io::{self,Cursor,Read};
 
 #[derive(Debug)]
 pub struct Reader<'a>{
-    data:&'a Vec<u8>,
+    data:&'a [u8],
+    path:Vec<PathSegment<'a>>,
+    options:DecodeOptions,
+    depth:usize,
+    total:usize,
+    header:Option<Header>,
 }
 
+#[derive(Debug)]
+pub enum PathSegment<'a>{
+    Key(&'a str),
+    Index(usize),
+}
+
+///
+/// a frame of the input, every read is confined to start..end of the frame
+/// so a length prefix can never reach into the bytes of a parent or sibling.
+///
 #[derive(Debug)]
 pub struct SubReader{
     start:usize,
@@ -16,114 +33,537 @@ pub struct SubReader{
 }
 
 impl SubReader{
-    pub fn sub(&mut self,global:&mut Reader,size:usize)->Result<SubReader,()>{
-        let start = self.start + self.cursor;
-        let end = start + size - 1;
-        // println!("sub : {:?} {:?} {:?} {:?}",start,end,self.cursor+size,size);
-        if end >= global.data.len(){
-            // println!("sub : {:?} {:?} {:?}",start,end,global.data.len());
-            return Err(());
+    pub fn offset(&self)->usize{
+        self.start + self.cursor
+    }
+    ///bytes left in this frame after the cursor
+    pub fn remaining(&self)->usize{
+        self.end - self.offset()
+    }
+    ///true if this frame holds no bytes, used by empty objects and vecs
+    pub fn is_empty(&self)->bool{
+        self.start == self.end
+    }
+    ///moves the cursor size bytes forward and returns the consumed range
+    fn take(&mut self,global:&Reader,size:usize)->Result<(usize,usize),DecodeError>{
+        let start = self.offset();
+        let end = match start.checked_add(size){
+            Some(v)=>{v},
+            None=>{return Err(DecodeError::LengthOverflow{len:size as u64,offset:start,path:global.path()});}
+        };
+        if end > self.end{
+            return Err(DecodeError::Truncated{offset:start,path:global.path()});
         }
         self.cursor += size;
-        return Ok(SubReader{
-            start:start,
-            end:end,
+        Ok((start,end))
+    }
+    pub fn sub(&mut self,global:&mut Reader,size:usize)->Result<SubReader,DecodeError>{
+        let (start,end) = self.take(global,size)?;
+        Ok(SubReader{
+            start,
+            end,
             cursor:0
-        });
-    }
-    pub fn read(&mut self,global:&mut Reader,size:usize)->Result<Vec<u8>,()>{
-        let start = self.start + self.cursor;
-        let end = start + size - 1;
-        
-        if end >= global.data.len(){
-            // println!("read : {:?} {:?} {:?}",start,end,global.data.len());
-            return Err(());
-        }
-        let mut build = vec![];
-        for i in start..=end{
-            build.push(global.data[i]);
+        })
+    }
+    pub fn read<'a>(&mut self,global:&mut Reader<'a>,size:usize)->Result<&'a [u8],DecodeError>{
+        let (start,end) = self.take(global,size)?;
+        global.total += size;
+        if global.total > global.options.max_total_bytes{
+            return Err(global.limit(Limit::TotalBytes,start));
         }
-        // println!("read : {:?} {:?} {:?} {:?} {:?} {:?}",start,end,self.cursor+size,size,self,build);
-        self.cursor += size;
-        return Ok(build);
-    }
-    pub fn read_full(&mut self,global:&mut Reader)->Result<Vec<u8>,()>{
-        let start = self.start;
-        let end = self.end;
-        if end >= global.data.len(){
-            // println!("read_full : {:?} {:?} {:?}",start,end,global.data.len());
-            return Err(());
+        Ok(&global.data[start..end])
+    }
+    pub fn read_full<'a>(&mut self,global:&mut Reader<'a>)->Result<&'a [u8],DecodeError>{
+        let size = self.remaining();
+        self.read(global,size)
+    }
+    ///next byte of the frame without consuming it
+    #[cfg(feature = "serde")]
+    pub fn peek(&self,global:&Reader)->Option<u8>{
+        if self.remaining() == 0{
+            return None;
         }
-        let mut build = vec![];
-        for i in start..=end{
-            build.push(global.data[i]);
+        Some(global.data[self.offset()])
+    }
+    ///errors if any bytes of this frame were not consumed
+    pub fn finish(&self,global:&mut Reader)->Result<(),DecodeError>{
+        if self.remaining() != 0{
+            return Err(DecodeError::TrailingBytes{offset:self.offset(),path:global.path()});
         }
-        // println!("read_full : {:?} {:?} {:?} {:?}",start,end,self,build);
-        self.cursor = end;
-        return Ok(build);
+        Ok(())
     }
 }
 
 impl <'a>Reader<'a>{
-    pub fn new(data:&Vec<u8>)->Reader{
+    pub fn new(data:&'a [u8],options:DecodeOptions)->Reader<'a>{
         Reader {
-            data:data
+            data,
+            path:vec![],
+            options,
+            depth:0,
+            total:0,
+            header:None
         }
     }
-    pub fn build(&mut self)->Result<DocValue,()>{
-        let mut sub = SubReader{
-            start:0,
-            end:self.data.len()-1,
+    ///
+    /// parses the optional header, checks the checksum trailer if the header
+    /// has one and returns the frame of the root data_line
+    ///
+    pub fn root(&mut self)->Result<SubReader,DecodeError>{
+        self.header = Header::parse(self.data)?;
+        let mut start = 0;
+        let mut end = self.data.len();
+        if let Some(header) = self.header{
+            start = HEADER_LEN;
+            if header.flags & FLAG_COMPRESSED != 0{
+                return Err(DecodeError::Compressed{offset:5});
+            }
+            if header.flags & FLAG_CHECKSUM != 0{
+                end = checksum::check(self.data)?;
+            }
+        }
+        Ok(SubReader{
+            start,
+            end,
             cursor:0
-        };
-        read_data_line(self,&mut sub)
+        })
+    }
+    pub fn build(&mut self)->Result<DocRef<'a>,DecodeError>{
+        let mut sub = self.root()?;
+        let value = read_data_line(self,&mut sub)?;
+        sub.finish(self)?;
+        Ok(value)
+    }
+    ///document path of the value currently being decoded e.g. sports.match.scores[2]
+    pub fn path(&self)->String{
+        let mut build = String::new();
+        for segment in self.path.iter(){
+            match segment{
+                PathSegment::Key(key)=>{
+                    if !build.is_empty(){build.push('.');}
+                    build.push_str(key);
+                },
+                PathSegment::Index(index)=>{
+                    build.push_str(&format!("[{}]",index));
+                }
+            }
+        }
+        build
+    }
+    ///true if objects and vecs store their entry count instead of continue bytes
+    pub fn counted(&self)->bool{
+        match self.header{
+            Some(v)=>{v.flags & FLAG_COUNTED != 0},
+            None=>{false}
+        }
+    }
+    ///format version of the document, 1 if it has no header
+    pub fn version(&self)->u8{
+        match self.header{
+            Some(v)=>{v.version},
+            None=>{1}
+        }
+    }
+    pub fn limit(&self,limit:Limit,offset:usize)->DecodeError{
+        DecodeError::LimitExceeded{limit,offset,path:self.path()}
+    }
+    ///called when entering a object or vec
+    pub fn enter(&mut self,offset:usize)->Result<(),DecodeError>{
+        self.depth += 1;
+        if self.depth > self.options.max_depth{
+            return Err(self.limit(Limit::Depth,offset));
+        }
+        Ok(())
+    }
+    ///called when leaving a object or vec
+    pub fn leave(&mut self){
+        self.depth -= 1;
+    }
+    pub fn push_path(&mut self,segment:PathSegment<'a>){
+        self.path.push(segment);
+    }
+    pub fn pop_path(&mut self){
+        self.path.pop();
     }
 }
 
-pub fn read_data_line(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+///
+/// reads exactly one document from a stream, the type and data_len_rep are
+/// pulled first so the size can be checked against the limits, then the
+/// data is read and the bytes of the whole document are returned for
+/// decoding. the stream is left after the last byte of the
+/// document. Eof is returned if the stream ends before the first byte and
+/// Truncated if it ends inside the document.
+///
+pub fn read_from<R:Read>(r:&mut R,options:DecodeOptions)->Result<Vec<u8>,DecodeError>{
 
-    // println!("\n\nread_data_line\n\n");
+    let mut build = vec![];
 
-    // println!("reader : {:?}",reader);
+    let mut version = 1;
+    let mut flags = 0;
+    pull(r,&mut build,1)?;
+    if build[0] == MAGIC[0]{
+        pull(r,&mut build,HEADER_LEN - 1)?;
+        if let Some(header) = Header::parse(&build)?{
+            version = header.version;
+            flags = header.flags;
+        }
+        if flags & FLAG_COMPRESSED != 0{
+            return read_block_from(r,build,flags,options);
+        }
+        pull(r,&mut build,1)?;
+    }
 
-    let data_type = reader.read(global,1)?;
-    let data_len_bytes = reader.read(global,8)?;
-    let data_len = bytes_to_u64(data_len_bytes)?;
-    let mut data = reader.sub(global,data_len as usize)?;
+    let len_start = build.len();
+    let len = if version == 1{
+        pull(r,&mut build,8)?;
+        match bytes_to_u64(&build[len_start..]){
+            Ok(v)=>{v},
+            Err(_)=>{return Err(DecodeError::Truncated{offset:len_start,path:String::new()});}
+        }
+    } else {
+        loop{
+            pull(r,&mut build,1)?;
+            if build[build.len() - 1] & 0x80 == 0{break;}
+            if build.len() - len_start >= VARINT_MAX{
+                return Err(DecodeError::LengthOverflow{len:u64::MAX,offset:len_start,path:String::new()});
+            }
+        }
+        match varint_to_u64(&build[len_start..]){
+            Some(v)=>{v},
+            None=>{return Err(DecodeError::LengthOverflow{len:u64::MAX,offset:len_start,path:String::new()});}
+        }
+    };
+    let head_len = build.len() as u64;
+    if len > (options.max_total_bytes as u64).saturating_sub(head_len){
+        return Err(DecodeError::LimitExceeded{limit:Limit::TotalBytes,offset:len_start,path:String::new()});
+    }
+
+    if let Err(error) = r.take(len).read_to_end(&mut build){
+        return Err(DecodeError::Io{error,offset:build.len()});
+    }
+    if (build.len() as u64) < head_len + len{
+        return Err(DecodeError::Truncated{offset:build.len(),path:String::new()});
+    }
+    if flags & FLAG_CHECKSUM != 0{
+        pull(r,&mut build,CHECKSUM_LEN)?;
+    }
+
+    Ok(build)
+
+}
+
+///reads the lengths and lz4 block of a compressed document after its header
+fn read_block_from<R:Read>(r:&mut R,mut build:Vec<u8>,flags:u8,options:DecodeOptions)->Result<Vec<u8>,DecodeError>{
+    pull(r,&mut build,16)?;
+    let len_start = HEADER_LEN + 8;
+    let len = match bytes_to_u64(&build[len_start..]){
+        Ok(v)=>{v},
+        Err(_)=>{return Err(DecodeError::Truncated{offset:len_start,path:String::new()});}
+    };
+    if len > (options.max_total_bytes as u64).saturating_sub(build.len() as u64){
+        return Err(DecodeError::LimitExceeded{limit:Limit::TotalBytes,offset:len_start,path:String::new()});
+    }
+    let head_len = build.len() as u64;
+    if let Err(error) = r.take(len).read_to_end(&mut build){
+        return Err(DecodeError::Io{error,offset:build.len()});
+    }
+    if (build.len() as u64) < head_len + len{
+        return Err(DecodeError::Truncated{offset:build.len(),path:String::new()});
+    }
+    if flags & FLAG_CHECKSUM != 0{
+        pull(r,&mut build,CHECKSUM_LEN)?;
+    }
+    Ok(build)
+}
+
+///appends exactly size bytes from the stream to build
+fn pull<R:Read>(r:&mut R,build:&mut Vec<u8>,size:usize)->Result<(),DecodeError>{
+    let start = build.len();
+    build.resize(start + size,0);
+    let mut filled = start;
+    while filled < build.len(){
+        match r.read(&mut build[filled..]){
+            Ok(0)=>{
+                if filled == 0{return Err(DecodeError::Eof);}
+                return Err(DecodeError::Truncated{offset:filled,path:String::new()});
+            },
+            Ok(n)=>{filled += n;},
+            Err(e) if e.kind() == io::ErrorKind::Interrupted=>{},
+            Err(error)=>{return Err(DecodeError::Io{error,offset:filled});}
+        }
+    }
+    Ok(())
+}
+
+///
+/// walks the encoded bytes along path without decoding unrelated values,
+/// object entries are matched by key and vec entries by index, the other
+/// entries are skipped with their length prefix. returns a frame positioned
+/// at the data_line of the target value or None if the path does not exist.
+///
+pub fn find_path<'a>(global:&mut Reader<'a>,path:&[&str])->Result<Option<SubReader>,DecodeError>{
+
+    let mut frame = global.root()?;
+
+    for segment in path{
+
+        let type_offset = frame.offset();
+        let data_type = frame.read(global,1)?;
+        let data_len = read_len(global,&mut frame)?;
+        let mut data = frame.sub(global,data_len)?;
+
+        let mut found = None;
+
+        if data_type[0] == 0{
+            let mut entries = Entries::new(global,&mut data)?;
+            while entries.next(global,&mut data)?{
+                let key = read_key(global,&mut data)?;
+                let entry = entry_frame(global,&mut data)?;
+                if key == *segment{
+                    global.path.push(PathSegment::Key(key));
+                    found = Some(entry);
+                    break;
+                }
+            }
+        } else if data_type[0] == 1{
+            let index:usize = match segment.parse(){
+                Ok(v)=>{v},
+                Err(_)=>{return Ok(None);}
+            };
+            let mut entries = Entries::new(global,&mut data)?;
+            if entries.count().is_some_and(|count|index >= count){
+                return Ok(None);
+            }
+            let mut current = 0;
+            while entries.next(global,&mut data)?{
+                let entry = entry_frame(global,&mut data)?;
+                if current == index{
+                    global.path.push(PathSegment::Index(index));
+                    found = Some(entry);
+                    break;
+                }
+                current += 1;
+            }
+        } else if data_type[0] > 7{
+            return Err(DecodeError::UnknownType{tag:data_type[0],offset:type_offset,path:global.path()});
+        }
+
+        match found{
+            Some(v)=>{frame = v;},
+            None=>{return Ok(None);}
+        }
+
+    }
+
+    Ok(Some(frame))
+
+}
+
+///
+/// number of entries of the object or vec data_line in frame, read from the
+/// count of a counted document, otherwise the entries are skipped one by one.
+/// none if the value is not a object or vec.
+///
+pub fn entry_count(global:&mut Reader,frame:&mut SubReader)->Result<Option<usize>,DecodeError>{
+
+    let type_offset = frame.offset();
+    let data_type = frame.read(global,1)?;
+    let data_len = read_len(global,frame)?;
+    let mut data = frame.sub(global,data_len)?;
+
+    if data_type[0] > 7{
+        return Err(DecodeError::UnknownType{tag:data_type[0],offset:type_offset,path:global.path()});
+    }
+    if data_type[0] > 1{
+        return Ok(None);
+    }
 
-    // println!("{:?} {:?} {:?} {:?} {:?}",data_type,data_len,data,reader.cursor,global.data.len());
+    let mut entries = Entries::new(global,&mut data)?;
+    if let Some(count) = entries.count{
+        return Ok(Some(count));
+    }
+    let mut count = 0;
+    while entries.next(global,&mut data)?{
+        if data_type[0] == 0{
+            read_key(global,&mut data)?;
+        }
+        entry_frame(global,&mut data)?;
+        count += 1;
+    }
+    Ok(Some(count))
+
+}
+
+///smallest encoded entry, a type tag and a one byte length (v2 null)
+const MIN_ENTRY_LEN:usize = 2;
+///most entries reserved up front, the rest grow as they are read
+const MAX_RESERVE:usize = 4096;
+
+///
+/// walks the entries of a object or vec frame, a counted document stores
+/// the number of entries first, otherwise every entry is followed by a
+/// continue byte and a empty frame has no entries.
+///
+pub struct Entries{
+    count:Option<usize>,
+    index:usize,
+}
+
+impl Entries{
+    pub fn new(global:&mut Reader,reader:&mut SubReader)->Result<Entries,DecodeError>{
+        if !global.counted(){
+            return Ok(Entries{count:None,index:0});
+        }
+        let offset = reader.offset();
+        let count = read_len(global,reader)?;
+        if count > global.options.max_entries{
+            return Err(global.limit(Limit::Entries,offset));
+        }
+        Ok(Entries{count:Some(count),index:0})
+    }
+    ///entry count of a counted object or vec
+    pub fn count(&self)->Option<usize>{
+        self.count
+    }
+    ///number of entries to reserve, never more than the bytes left in the frame can hold
+    pub fn capacity(&self,reader:&SubReader)->usize{
+        match self.count{
+            Some(count)=>{count.min(reader.remaining() / MIN_ENTRY_LEN).min(MAX_RESERVE)},
+            None=>{0}
+        }
+    }
+    ///true if another entry follows, reads the continue byte of the previous entry
+    pub fn next(&mut self,global:&mut Reader,reader:&mut SubReader)->Result<bool,DecodeError>{
+        match self.count{
+            Some(count)=>{
+                let more = self.index < count;
+                if more != (reader.remaining() != 0){
+                    return Err(DecodeError::CountMismatch{count,offset:reader.offset(),path:global.path()});
+                }
+                if !more{return Ok(false);}
+            },
+            None=>{
+                if self.index == 0{
+                    if reader.is_empty(){return Ok(false);}
+                } else if !read_continue_byte(global,reader)?{
+                    return Ok(false);
+                }
+            }
+        }
+        self.index += 1;
+        if self.index > global.options.max_entries{
+            return Err(global.limit(Limit::Entries,reader.offset()));
+        }
+        Ok(true)
+    }
+}
 
-    process_data(data_type[0],global, &mut data)
+///reads the data_len_rep and utf8 key of a object entry
+pub fn read_key<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<&'a str,DecodeError>{
+    let len_offset = reader.offset();
+    let key_len = read_len(global,reader)?;
+    if key_len > global.options.max_len{
+        return Err(global.limit(Limit::Length,len_offset));
+    }
+    let key_offset = reader.offset();
+    let key_bytes = reader.read(global,key_len)?;
+    match std::str::from_utf8(key_bytes){
+        Ok(v)=>{Ok(v)},
+        Err(_)=>{Err(DecodeError::InvalidKey{offset:key_offset,path:global.path()})}
+    }
+}
+
+///
+/// frame holding the data_line of the next object or vec entry, in version 1
+/// the data_line is wrapped in its own data_len_rep, from version 2 on the
+/// data_line follows directly and is measured by its own head.
+///
+pub fn entry_frame(global:&mut Reader,reader:&mut SubReader)->Result<SubReader,DecodeError>{
+    if global.version() == 1{
+        let entry_len = read_len(global,reader)?;
+        return reader.sub(global,entry_len);
+    }
+    let start = reader.offset();
+    reader.read(global,1)?;
+    let data_len = read_len(global,reader)?;
+    reader.sub(global,data_len)?;
+    Ok(SubReader{
+        start,
+        end:reader.offset(),
+        cursor:0
+    })
+}
+
+///decodes the value of the next object or vec entry
+fn read_entry<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
+    if global.version() == 1{
+        let entry_len = read_len(global,reader)?;
+        let mut entry = reader.sub(global,entry_len)?;
+        let value = read_data_line(global,&mut entry)?;
+        entry.finish(global)?;
+        return Ok(value);
+    }
+    read_data_line(global,reader)
+}
+
+pub fn read_data_line<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
+
+    let (data_type,type_offset,mut data) = read_head(global,reader)?;
+
+    let value = process_data(data_type,type_offset,global, &mut data)?;
+    data.finish(global)?;
+    Ok(value)
 
 }
 
-pub fn process_data(data_type:u8,global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+///reads the data_type and data_len_rep of a data_line, returns the type, its offset and the frame of the data
+pub fn read_head(global:&mut Reader,reader:&mut SubReader)->Result<(u8,usize,SubReader),DecodeError>{
+    let type_offset = reader.offset();
+    let data_type = reader.read(global,1)?;
+    let data_len = read_len(global,reader)?;
+    if (data_type[0] == 2 || data_type[0] == 3) && data_len > global.options.max_len{
+        return Err(global.limit(Limit::Length,type_offset));
+    }
+    let data = reader.sub(global,data_len)?;
+    Ok((data_type[0],type_offset,data))
+}
+
+pub fn process_data<'a>(data_type:u8,type_offset:usize,global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
 
     if data_type == 0{
-        return process_object(global, reader);
+        process_object(global, reader)
     } else if data_type == 1{
-        return process_vec(global, reader);
+        process_vec(global, reader)
     } else if data_type == 2{
-        return process_binary(global, reader);
+        process_binary(global, reader)
     } else if data_type == 3{
-        return process_string(global, reader);
+        process_string(global, reader)
     } else if data_type == 4{
-        return process_num(global, reader);
+        process_num(global, reader)
     } else if data_type == 5{
-        return process_float(global, reader);
+        process_float(global, reader)
     } else if data_type == 6{
-        return process_bool(global, reader);
+        process_bool(global, reader)
     } else if data_type == 7{
-        return process_null(global, reader);
+        process_null(global, reader)
     } else {
-        return Err(());
+        Err(DecodeError::UnknownType{tag:data_type,offset:type_offset,path:global.path()})
     }
 
 }
 
 /*
 
+header - optional, magic("GZBD") version(u8) flags(u8) followed by the root data_line
+    version 1 - default without header, data_len_rep is a fixed u64
+    version 2 - data_len_rep is a varint and object and vec entries are not wrapped in a data_len_rep
+    flags - bit 0 counted, objects and vecs start with their entry count and have no continue bytes
+    flags - bit 1 checksum, the root data_line is followed by a crc32c(u32 big endien) of the header and root data_line
+    flags - bit 2 compressed, the root data_line is replaced by u64 data length, u64 block length and a lz4 block, the checksum covers the block
+
 data_len_rep - u64 num representing length of data in bytes represented in u64 bytes big endien
+    version 2 - unsigned LEB128 varint, 7 bits per byte low bits first, high bit set if more bytes follow
 
 continue_byte - if 1 next is key if 0 this is last key
 
@@ -142,6 +582,10 @@ data_line - data_type data_len_rep data(nested as per data parse)
 data_parse 
     object - **repeating pattern** data_len_rep key data_len_rep data continue_byte(if 1 next is key if 0 this is last key)
     vec - **repeating pattern** data_len_rep data continue_byte
+    version 2 object - **repeating pattern** data_len_rep key data_line continue_byte
+    version 2 vec - **repeating pattern** data_line continue_byte
+    counted object or vec - data_len_rep(entry count) then the entries without continue_byte, also for empty ones
+    empty object or vec - no data, data_len_rep is 0
     binary - data(vec<u8>)
     string - data(utf8 string as bytes)
     num - data(i64 num as bytes big endien)
@@ -150,117 +594,202 @@ data_parse
     null - data(0 as byte)  - 1 byte
 */
 
-pub fn process_object(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+pub fn process_object<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
 
-    // println!("\n\nprocess_object\n\n");
+    global.enter(reader.offset())?;
 
-    let mut map:HashMap<String, DocValue> = HashMap::new();
+    let mut entries = Entries::new(global,reader)?;
+    let mut map:Map<&'a str,DocRef<'a>> = Map::with_capacity(entries.capacity(reader));
 
-    loop{
+    while entries.next(global,reader)?{
 
-        let key_len_bytes = reader.read(global,8)?;
-        let key_len = bytes_to_u64(key_len_bytes.clone())?;
-        let key_bytes = reader.read(global,key_len as usize)?;
-        let key = bytes_to_string(key_bytes.clone())?;
+        let key = read_key(global,reader)?;
 
-        // println!("key : {:?}",key);
-
-        let data_len_bytes = reader.read(global,8)?;
-        let data_len = bytes_to_u64(data_len_bytes.clone())?;
-        let mut data_reader = reader.sub(global,data_len as usize)?;
-        let data = read_data_line(global,&mut data_reader)?;
-
-        // println!("data : {:?}",data);
+        global.push_path(PathSegment::Key(key));
+        let data = read_entry(global,reader)?;
+        global.pop_path();
 
         map.insert(key,data);
 
-        let continue_byte = reader.read(global,1)?;
-        if continue_byte.len() != 1{return Err(());}
-        if continue_byte[0] == 1{} else if continue_byte[0] == 0{break;} else {return Err(());}
-
     }
 
-    return Ok(DocValue::Object(map));
+    global.leave();
+
+    Ok(DocRef::Object(map))
 
 }
-pub fn process_vec(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+pub fn process_vec<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
 
-    let mut build = vec![];
+    global.enter(reader.offset())?;
 
-    loop{
+    let mut entries = Entries::new(global,reader)?;
+    let mut build = Vec::with_capacity(entries.capacity(reader));
 
-        let len_rep_bytes = reader.read(global,8)?;
-        let len_rep = bytes_to_u64(len_rep_bytes)?;
-        let mut data_reader = reader.sub(global,len_rep as usize)?;
-        let data = read_data_line(global,&mut data_reader)?;
+    while entries.next(global,reader)?{
+
+        global.push_path(PathSegment::Index(build.len()));
+        let data = read_entry(global,reader)?;
+        global.pop_path();
         build.push(data);
-        let continue_byte = reader.read(global,1)?;
-        if continue_byte.len() != 1{return Err(());}
-        if continue_byte[0] == 1{} else if continue_byte[0] == 0{break;} else {return Err(());}
 
     }
 
-    return Ok(DocValue::Vec(build));
+    global.leave();
+
+    Ok(DocRef::Vec(build))
 
-    // Err(())
 }
-pub fn process_binary(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+pub fn process_binary<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
     let as_bytes = reader.read_full(global)?;
-    return Ok(DocValue::Binary(as_bytes));
+    Ok(DocRef::Binary(as_bytes))
 }
-pub fn process_string(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+pub fn process_string<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
+    let offset = reader.offset();
     let as_bytes = reader.read_full(global)?;
-    let as_value = bytes_to_string(as_bytes)?; 
-    return Ok(DocValue::string(as_value));
+    match std::str::from_utf8(as_bytes){
+        Ok(v)=>{Ok(DocRef::String(v))},
+        Err(_)=>{Err(DecodeError::InvalidString{offset,path:global.path()})}
+    }
 }
-pub fn process_num(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+pub fn process_num<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
+    let offset = reader.offset();
     let as_bytes = reader.read_full(global)?;
-    let as_value = bytes_to_i64(as_bytes)?; 
-    return Ok(DocValue::Num(as_value));
+    match bytes_to_i64(as_bytes){
+        Ok(v)=>{Ok(DocRef::Num(v))},
+        Err(_)=>{Err(DecodeError::InvalidNum{offset,path:global.path()})}
+    }
 }
-pub fn process_float(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+pub fn process_float<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
+    let offset = reader.offset();
     let as_bytes = reader.read_full(global)?;
-    let as_value = bytes_to_f64(as_bytes)?; 
-    return Ok(DocValue::Float(as_value));
+    match bytes_to_f64(as_bytes){
+        Ok(v)=>{Ok(DocRef::Float(v))},
+        Err(_)=>{Err(DecodeError::InvalidFloat{offset,path:global.path()})}
+    }
 }
-pub fn process_bool(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+pub fn process_bool<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
+    let offset = reader.offset();
     let as_bytes = reader.read_full(global)?;
-    if as_bytes.len() != 1{return Err(());}
+    if as_bytes.len() != 1{return Err(DecodeError::InvalidBool{offset,path:global.path()});}
     let hold:bool;
-    if as_bytes[0] == 0{hold = false;} else if as_bytes[0] == 1{hold = true;} else {return Err(());}
-    return Ok(DocValue::Bool(hold));
+    if as_bytes[0] == 0{hold = false;} else if as_bytes[0] == 1{hold = true;} else {return Err(DecodeError::InvalidBool{offset,path:global.path()});}
+    Ok(DocRef::Bool(hold))
 }
-pub fn process_null(global:&mut Reader,reader:&mut SubReader)->Result<DocValue,()>{
+pub fn process_null<'a>(global:&mut Reader<'a>,reader:&mut SubReader)->Result<DocRef<'a>,DecodeError>{
+    let offset = reader.offset();
     let as_bytes = reader.read_full(global)?;
-    if as_bytes.len() != 1{return Err(());}
-    if as_bytes[0] != 0{return Err(());}
-    return Ok(DocValue::Null);
+    if as_bytes.len() != 1 || as_bytes[0] != 0{return Err(DecodeError::InvalidNull{offset,path:global.path()});}
+    Ok(DocRef::Null)
 }
 
-pub fn bytes_to_i64(v:Vec<u8>)->Result<i64,()>{
+///reads a data_len_rep and checks it fits in a usize
+pub fn read_len(global:&mut Reader,reader:&mut SubReader)->Result<usize,DecodeError>{
+    let offset = reader.offset();
+    let len = if global.version() == 1{
+        let len_bytes = reader.read(global,8)?;
+        match bytes_to_u64(len_bytes){
+            Ok(v)=>{v},
+            Err(_)=>{return Err(DecodeError::Truncated{offset,path:global.path()});}
+        }
+    } else {
+        let available = &global.data[offset..reader.end];
+        let size = match available.iter().position(|b|b & 0x80 == 0){
+            Some(v)=>{v + 1},
+            None if available.len() < VARINT_MAX=>{return Err(DecodeError::Truncated{offset,path:global.path()});},
+            None=>{VARINT_MAX + 1}
+        };
+        if size > VARINT_MAX{
+            return Err(DecodeError::LengthOverflow{len:u64::MAX,offset,path:global.path()});
+        }
+        let len_bytes = reader.read(global,size)?;
+        match varint_to_u64(len_bytes){
+            Some(v)=>{v},
+            None=>{return Err(DecodeError::LengthOverflow{len:u64::MAX,offset,path:global.path()});}
+        }
+    };
+    match usize::try_from(len){
+        Ok(v)=>{Ok(v)},
+        Err(_)=>{Err(DecodeError::LengthOverflow{len,offset,path:global.path()})}
+    }
+}
+
+///reads the continue byte after a object or vec entry, true if another entry follows
+pub fn read_continue_byte(global:&mut Reader,reader:&mut SubReader)->Result<bool,DecodeError>{
+    let offset = reader.offset();
+    let continue_byte = reader.read(global,1)?;
+    if continue_byte[0] == 1{
+        Ok(true)
+    } else if continue_byte[0] == 0{
+        Ok(false)
+    } else {
+        Err(DecodeError::BadContinueByte{byte:continue_byte[0],offset,path:global.path()})
+    }
+}
+
+///longest LEB128 encoding of a u64
+const VARINT_MAX:usize = 10;
+
+///decodes a complete LEB128 varint, None if it is unterminated or overflows a u64
+fn varint_to_u64(v:&[u8])->Option<u64>{
+    let mut value:u64 = 0;
+    for (index,byte) in v.iter().enumerate(){
+        if index == VARINT_MAX - 1 && *byte > 1{return None;}
+        value |= ((byte & 0x7f) as u64) << (7 * index);
+        if byte & 0x80 == 0{
+            if index + 1 != v.len(){return None;}
+            return Some(value);
+        }
+    }
+    None
+}
+
+///
+/// checks that data is exactly what canonical encoding writes for the
+/// document it holds, in the format, header and flags data was written
+/// with. Ok(false) for valid documents with unsorted keys, non canonical
+/// floats or duplicate keys, errors if data is not a valid document.
+///
+/// let bin = person.write_canonical();
+/// assert!(gzbbinarydoc::is_canonical(&bin).unwrap());
+///
+pub fn is_canonical(data:&[u8])->Result<bool,DecodeError>{
+    let doc = crate::DocValue::read(data)?;
+    let mut options = crate::EncodeOptions{canonical:true,..crate::EncodeOptions::default()};
+    if let Some(header) = Header::parse(data)?{
+        options.header = true;
+        if header.version == 2{
+            options.format = crate::Format::V2;
+        }
+        options.counted = header.flags & FLAG_COUNTED != 0;
+        options.checksum = header.flags & FLAG_CHECKSUM != 0;
+        #[cfg(feature = "compression")]
+        if header.flags & FLAG_COMPRESSED != 0{
+            options.compression = Some(0);
+        }
+    }
+    Ok(doc.write_with(options) == data)
+}
+
+pub fn bytes_to_i64(v:&[u8])->Result<i64,()>{
+    if v.len() != 8{return Err(());}
     let mut rdr = Cursor::new(v);
     match rdr.read_i64::<BigEndian>(){
         Ok(v)=>{Ok(v)},
         Err(_)=>{Err(())}
     }
 }
-fn bytes_to_f64(v:Vec<u8>)->Result<f64,()>{
+fn bytes_to_f64(v:&[u8])->Result<f64,()>{
+    if v.len() != 8{return Err(());}
     let mut rdr = Cursor::new(v);
     match rdr.read_f64::<BigEndian>(){
         Ok(v)=>{Ok(v)},
         Err(_)=>{Err(())}
     }
 }
-fn bytes_to_u64(v:Vec<u8>)->Result<u64,()>{
+fn bytes_to_u64(v:&[u8])->Result<u64,()>{
     let mut rdr = Cursor::new(v);
     match rdr.read_u64::<BigEndian>(){
         Ok(v)=>{Ok(v)},
         Err(_)=>{Err(())}
     }
 }
-fn bytes_to_string(v:Vec<u8>)->Result<String,()>{
-    match String::from_utf8(v){
-        Ok(v)=>{Ok(v)},
-        Err(_)=>{Err(())}
-    }
-}
\ No newline at end of file
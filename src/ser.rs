@@ -0,0 +1,420 @@
+use crate::{DocValue,SerdeError};
+use serde::ser::{self,Serialize,SerializeMap,SerializeSeq};
+
+///
+/// encodes any Serialize type straight into the version 1 wire format
+/// without building a DocValue, the output reads back with DocValue::read
+/// and from_bytes. structs and maps become objects with their fields in
+/// order, sequences and tuples become vecs, serialize_bytes becomes binary
+/// and None and unit become null. enum variants are written like serde_json
+/// does, unit variants as a string and the others as a object holding the
+/// variant name as its only key.
+///
+/// let bin = gzbbinarydoc::to_bytes(&person)?;
+///
+pub fn to_bytes<T:Serialize + ?Sized>(value:&T)->Result<Vec<u8>,SerdeError>{
+    let mut serializer = Serializer{out:vec![]};
+    value.serialize(&mut serializer)?;
+    Ok(serializer.out)
+}
+
+///
+/// lengths are not known before a object or vec is serialized so a zero
+/// data_len_rep is written first and patched when the value is done.
+///
+struct Serializer{
+    out:Vec<u8>,
+}
+
+impl Serializer{
+    fn data_line(&mut self,data_type:u8,data:&[u8]){
+        self.out.push(data_type);
+        self.out.extend_from_slice(&(data.len() as u64).to_be_bytes());
+        self.out.extend_from_slice(data);
+    }
+    ///writes a zero data_len_rep and returns its position for end_len
+    fn begin_len(&mut self)->usize{
+        let position = self.out.len();
+        self.out.extend_from_slice(&[0;8]);
+        position
+    }
+    ///patches the data_len_rep at position with the bytes written after it
+    fn end_len(&mut self,position:usize){
+        let len = (self.out.len() - position - 8) as u64;
+        self.out[position..position + 8].copy_from_slice(&len.to_be_bytes());
+    }
+    fn begin_line(&mut self,data_type:u8)->usize{
+        self.out.push(data_type);
+        self.begin_len()
+    }
+    fn key(&mut self,key:&str){
+        self.out.extend_from_slice(&(key.len() as u64).to_be_bytes());
+        self.out.extend_from_slice(key.as_bytes());
+    }
+    fn num(&mut self,v:i64)->Result<(),SerdeError>{
+        self.data_line(4,&v.to_be_bytes());
+        Ok(())
+    }
+    ///opens the object holding a enum variant as its only key
+    fn begin_variant(&mut self,variant:&str)->Variant{
+        let line = self.begin_line(0);
+        self.key(variant);
+        let entry = self.begin_len();
+        Variant{line,entry}
+    }
+    fn end_variant(&mut self,variant:Variant){
+        self.end_len(variant.entry);
+        self.out.push(0);
+        self.end_len(variant.line);
+    }
+}
+
+///positions of the data_len_reps of a enum variant object and its entry
+#[derive(Clone,Copy)]
+struct Variant{
+    line:usize,
+    entry:usize,
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer{
+
+    type Ok = ();
+    type Error = SerdeError;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self,v:bool)->Result<(),SerdeError>{
+        self.data_line(6,&[v as u8]);
+        Ok(())
+    }
+    fn serialize_i8(self,v:i8)->Result<(),SerdeError>{self.num(v as i64)}
+    fn serialize_i16(self,v:i16)->Result<(),SerdeError>{self.num(v as i64)}
+    fn serialize_i32(self,v:i32)->Result<(),SerdeError>{self.num(v as i64)}
+    fn serialize_i64(self,v:i64)->Result<(),SerdeError>{self.num(v)}
+    fn serialize_i128(self,v:i128)->Result<(),SerdeError>{
+        match i64::try_from(v){
+            Ok(v)=>{self.num(v)},
+            Err(_)=>{Err(SerdeError::Message(format!("i128 {} does not fit in a i64 num",v)))}
+        }
+    }
+    fn serialize_u8(self,v:u8)->Result<(),SerdeError>{self.num(v as i64)}
+    fn serialize_u16(self,v:u16)->Result<(),SerdeError>{self.num(v as i64)}
+    fn serialize_u32(self,v:u32)->Result<(),SerdeError>{self.num(v as i64)}
+    fn serialize_u64(self,v:u64)->Result<(),SerdeError>{
+        match i64::try_from(v){
+            Ok(v)=>{self.num(v)},
+            Err(_)=>{Err(SerdeError::Message(format!("u64 {} does not fit in a i64 num",v)))}
+        }
+    }
+    fn serialize_u128(self,v:u128)->Result<(),SerdeError>{
+        match i64::try_from(v){
+            Ok(v)=>{self.num(v)},
+            Err(_)=>{Err(SerdeError::Message(format!("u128 {} does not fit in a i64 num",v)))}
+        }
+    }
+    fn serialize_f32(self,v:f32)->Result<(),SerdeError>{
+        self.serialize_f64(v as f64)
+    }
+    fn serialize_f64(self,v:f64)->Result<(),SerdeError>{
+        self.data_line(5,&v.to_be_bytes());
+        Ok(())
+    }
+    fn serialize_char(self,v:char)->Result<(),SerdeError>{
+        self.serialize_str(v.encode_utf8(&mut [0;4]))
+    }
+    fn serialize_str(self,v:&str)->Result<(),SerdeError>{
+        self.data_line(3,v.as_bytes());
+        Ok(())
+    }
+    fn serialize_bytes(self,v:&[u8])->Result<(),SerdeError>{
+        self.data_line(2,v);
+        Ok(())
+    }
+    fn serialize_none(self)->Result<(),SerdeError>{
+        self.serialize_unit()
+    }
+    fn serialize_some<T:Serialize + ?Sized>(self,value:&T)->Result<(),SerdeError>{
+        value.serialize(self)
+    }
+    fn serialize_unit(self)->Result<(),SerdeError>{
+        self.data_line(7,&[0]);
+        Ok(())
+    }
+    fn serialize_unit_struct(self,_name:&'static str)->Result<(),SerdeError>{
+        self.serialize_unit()
+    }
+    fn serialize_unit_variant(self,_name:&'static str,_index:u32,variant:&'static str)->Result<(),SerdeError>{
+        self.serialize_str(variant)
+    }
+    fn serialize_newtype_struct<T:Serialize + ?Sized>(self,_name:&'static str,value:&T)->Result<(),SerdeError>{
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T:Serialize + ?Sized>(self,_name:&'static str,_index:u32,variant:&'static str,value:&T)->Result<(),SerdeError>{
+        let hold = self.begin_variant(variant);
+        value.serialize(&mut *self)?;
+        self.end_variant(hold);
+        Ok(())
+    }
+    fn serialize_seq(self,_len:Option<usize>)->Result<Compound<'a>,SerdeError>{
+        Ok(Compound::new(self,1,None))
+    }
+    fn serialize_tuple(self,_len:usize)->Result<Compound<'a>,SerdeError>{
+        Ok(Compound::new(self,1,None))
+    }
+    fn serialize_tuple_struct(self,_name:&'static str,_len:usize)->Result<Compound<'a>,SerdeError>{
+        Ok(Compound::new(self,1,None))
+    }
+    fn serialize_tuple_variant(self,_name:&'static str,_index:u32,variant:&'static str,_len:usize)->Result<Compound<'a>,SerdeError>{
+        let hold = self.begin_variant(variant);
+        Ok(Compound::new(self,1,Some(hold)))
+    }
+    fn serialize_map(self,_len:Option<usize>)->Result<Compound<'a>,SerdeError>{
+        Ok(Compound::new(self,0,None))
+    }
+    fn serialize_struct(self,_name:&'static str,_len:usize)->Result<Compound<'a>,SerdeError>{
+        Ok(Compound::new(self,0,None))
+    }
+    fn serialize_struct_variant(self,_name:&'static str,_index:u32,variant:&'static str,_len:usize)->Result<Compound<'a>,SerdeError>{
+        let hold = self.begin_variant(variant);
+        Ok(Compound::new(self,0,Some(hold)))
+    }
+    fn is_human_readable(&self)->bool{
+        false
+    }
+
+}
+
+///a object or vec being serialized
+struct Compound<'a>{
+    ser:&'a mut Serializer,
+    line:usize,
+    last_continue:Option<usize>,
+    variant:Option<Variant>,
+}
+
+impl<'a> Compound<'a>{
+    fn new(ser:&'a mut Serializer,data_type:u8,variant:Option<Variant>)->Compound<'a>{
+        let line = ser.begin_line(data_type);
+        Compound{ser,line,last_continue:None,variant}
+    }
+    ///
+    /// writes one entry followed by a continue byte of 1, the continue
+    /// byte of the last entry is set to 0 in end.
+    ///
+    fn entry<T:Serialize + ?Sized>(&mut self,value:&T)->Result<(),SerdeError>{
+        let entry = self.ser.begin_len();
+        value.serialize(&mut *self.ser)?;
+        self.ser.end_len(entry);
+        self.last_continue = Some(self.ser.out.len());
+        self.ser.out.push(1);
+        Ok(())
+    }
+    fn end(self)->Result<(),SerdeError>{
+        if let Some(position) = self.last_continue{
+            self.ser.out[position] = 0;
+        }
+        self.ser.end_len(self.line);
+        if let Some(variant) = self.variant{
+            self.ser.end_variant(variant);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a>{
+    type Ok = ();
+    type Error = SerdeError;
+    fn serialize_element<T:Serialize + ?Sized>(&mut self,value:&T)->Result<(),SerdeError>{
+        self.entry(value)
+    }
+    fn end(self)->Result<(),SerdeError>{
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a>{
+    type Ok = ();
+    type Error = SerdeError;
+    fn serialize_element<T:Serialize + ?Sized>(&mut self,value:&T)->Result<(),SerdeError>{
+        self.entry(value)
+    }
+    fn end(self)->Result<(),SerdeError>{
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a>{
+    type Ok = ();
+    type Error = SerdeError;
+    fn serialize_field<T:Serialize + ?Sized>(&mut self,value:&T)->Result<(),SerdeError>{
+        self.entry(value)
+    }
+    fn end(self)->Result<(),SerdeError>{
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a>{
+    type Ok = ();
+    type Error = SerdeError;
+    fn serialize_field<T:Serialize + ?Sized>(&mut self,value:&T)->Result<(),SerdeError>{
+        self.entry(value)
+    }
+    fn end(self)->Result<(),SerdeError>{
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a>{
+    type Ok = ();
+    type Error = SerdeError;
+    fn serialize_key<T:Serialize + ?Sized>(&mut self,key:&T)->Result<(),SerdeError>{
+        key.serialize(KeySerializer{ser:&mut *self.ser})
+    }
+    fn serialize_value<T:Serialize + ?Sized>(&mut self,value:&T)->Result<(),SerdeError>{
+        self.entry(value)
+    }
+    fn end(self)->Result<(),SerdeError>{
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a>{
+    type Ok = ();
+    type Error = SerdeError;
+    fn serialize_field<T:Serialize + ?Sized>(&mut self,key:&'static str,value:&T)->Result<(),SerdeError>{
+        self.ser.key(key);
+        self.entry(value)
+    }
+    fn end(self)->Result<(),SerdeError>{
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a>{
+    type Ok = ();
+    type Error = SerdeError;
+    fn serialize_field<T:Serialize + ?Sized>(&mut self,key:&'static str,value:&T)->Result<(),SerdeError>{
+        self.ser.key(key);
+        self.entry(value)
+    }
+    fn end(self)->Result<(),SerdeError>{
+        Compound::end(self)
+    }
+}
+
+///
+/// writes map keys, strings and chars are written as they are, integers
+/// and unit variants as their text like serde_json does.
+///
+struct KeySerializer<'a>{
+    ser:&'a mut Serializer,
+}
+
+impl<'a> KeySerializer<'a>{
+    fn text<T:ToString>(self,v:T)->Result<(),SerdeError>{
+        self.ser.key(&v.to_string());
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for KeySerializer<'a>{
+
+    type Ok = ();
+    type Error = SerdeError;
+
+    type SerializeSeq = ser::Impossible<(),SerdeError>;
+    type SerializeTuple = ser::Impossible<(),SerdeError>;
+    type SerializeTupleStruct = ser::Impossible<(),SerdeError>;
+    type SerializeTupleVariant = ser::Impossible<(),SerdeError>;
+    type SerializeMap = ser::Impossible<(),SerdeError>;
+    type SerializeStruct = ser::Impossible<(),SerdeError>;
+    type SerializeStructVariant = ser::Impossible<(),SerdeError>;
+
+    fn serialize_str(self,v:&str)->Result<(),SerdeError>{
+        self.ser.key(v);
+        Ok(())
+    }
+    fn serialize_char(self,v:char)->Result<(),SerdeError>{self.serialize_str(v.encode_utf8(&mut [0;4]))}
+    fn serialize_i8(self,v:i8)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_i16(self,v:i16)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_i32(self,v:i32)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_i64(self,v:i64)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_i128(self,v:i128)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_u8(self,v:u8)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_u16(self,v:u16)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_u32(self,v:u32)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_u64(self,v:u64)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_u128(self,v:u128)->Result<(),SerdeError>{self.text(v)}
+    fn serialize_unit_variant(self,_name:&'static str,_index:u32,variant:&'static str)->Result<(),SerdeError>{
+        self.serialize_str(variant)
+    }
+    fn serialize_newtype_struct<T:Serialize + ?Sized>(self,_name:&'static str,value:&T)->Result<(),SerdeError>{
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self,_v:bool)->Result<(),SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_f32(self,_v:f32)->Result<(),SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_f64(self,_v:f64)->Result<(),SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_bytes(self,_v:&[u8])->Result<(),SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_none(self)->Result<(),SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_some<T:Serialize + ?Sized>(self,_value:&T)->Result<(),SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_unit(self)->Result<(),SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_unit_struct(self,_name:&'static str)->Result<(),SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_newtype_variant<T:Serialize + ?Sized>(self,_name:&'static str,_index:u32,_variant:&'static str,_value:&T)->Result<(),SerdeError>{
+        Err(SerdeError::KeyNotString)
+    }
+    fn serialize_seq(self,_len:Option<usize>)->Result<Self::SerializeSeq,SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_tuple(self,_len:usize)->Result<Self::SerializeTuple,SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_tuple_struct(self,_name:&'static str,_len:usize)->Result<Self::SerializeTupleStruct,SerdeError>{
+        Err(SerdeError::KeyNotString)
+    }
+    fn serialize_tuple_variant(self,_name:&'static str,_index:u32,_variant:&'static str,_len:usize)->Result<Self::SerializeTupleVariant,SerdeError>{
+        Err(SerdeError::KeyNotString)
+    }
+    fn serialize_map(self,_len:Option<usize>)->Result<Self::SerializeMap,SerdeError>{Err(SerdeError::KeyNotString)}
+    fn serialize_struct(self,_name:&'static str,_len:usize)->Result<Self::SerializeStruct,SerdeError>{
+        Err(SerdeError::KeyNotString)
+    }
+    fn serialize_struct_variant(self,_name:&'static str,_index:u32,_variant:&'static str,_len:usize)->Result<Self::SerializeStructVariant,SerdeError>{
+        Err(SerdeError::KeyNotString)
+    }
+
+}
+
+///
+/// Num is written as a i64, Float as a f64, Binary as bytes and Null as
+/// unit, so DocValue can pass through any serde format.
+///
+impl Serialize for DocValue{
+    fn serialize<S:ser::Serializer>(&self,serializer:S)->Result<S::Ok,S::Error>{
+        match self{
+            DocValue::Object(map)=>{
+                let mut build = serializer.serialize_map(Some(map.len()))?;
+                for (key,value) in map.iter(){
+                    build.serialize_entry(key,value)?;
+                }
+                build.end()
+            },
+            DocValue::Vec(pool)=>{
+                let mut build = serializer.serialize_seq(Some(pool.len()))?;
+                for item in pool{
+                    build.serialize_element(item)?;
+                }
+                build.end()
+            },
+            DocValue::Binary(v)=>{serializer.serialize_bytes(v)},
+            DocValue::String(v)=>{serializer.serialize_str(v)},
+            DocValue::Num(v)=>{serializer.serialize_i64(*v)},
+            DocValue::Float(v)=>{serializer.serialize_f64(*v)},
+            DocValue::Bool(v)=>{serializer.serialize_bool(*v)},
+            DocValue::Null=>{serializer.serialize_unit()},
+        }
+    }
+}
@@ -1,34 +1,85 @@
-
 use crate::{DocValue};
 use byteorder::{BigEndian, WriteBytesExt};
+use std::io::{self,Write};
+use crate::{Map,MapIter};
+use crate::header::{Header,FLAG_COUNTED,FLAG_CHECKSUM};
+use crate::checksum::{ChecksumWriter,Crc32c};
+use crate::EncodeOptions;
 
 pub fn write(doc:&DocValue)->Vec<u8>{
-    process_value(doc)
+    write_with(doc,EncodeOptions::default())
+}
+
+pub fn write_with(doc:&DocValue,options:EncodeOptions)->Vec<u8>{
+    let mut build = vec![];
+    write_to_with(doc,&mut build,options).unwrap();
+    build
+}
+
+pub fn write_to<W:Write>(doc:&DocValue,out:&mut W)->io::Result<()>{
+    write_to_with(doc,out,EncodeOptions::default())
+}
+
+pub fn write_to_with<W:Write>(doc:&DocValue,out:&mut W,options:EncodeOptions)->io::Result<()>{
+    let layout = Layout{version:options.format.version(),counted:options.counted,canonical:options.canonical};
+    let mut sizes = vec![];
+    let root_len = layout.line_len(doc,&mut sizes);
+    let mut flags = 0;
+    if layout.counted{flags |= FLAG_COUNTED;}
+    if options.checksum{flags |= FLAG_CHECKSUM;}
+    #[cfg(feature = "compression")]
+    if options.compression.is_some_and(|threshold|root_len >= threshold){
+        flags |= crate::header::FLAG_COMPRESSED;
+    }
+    let document = Document{doc,layout,flags,sizes,root_len};
+    if options.checksum{
+        let mut out = ChecksumWriter{out,crc:Crc32c::new()};
+        out.write_all(&Header::new(layout.version,flags).to_bytes())?;
+        document.write_body(&mut out)?;
+        let crc = out.crc.finish();
+        return out.out.write_all(&crc.to_be_bytes());
+    }
+    if options.header || layout.version != 1 || flags != 0{
+        out.write_all(&Header::new(layout.version,flags).to_bytes())?;
+    }
+    document.write_body(out)
 }
 
-fn process_value(value:&DocValue)->Vec<u8>{
-    if value.self_is_object(){
-        return process_object(value);
-    } else if value.self_is_vec(){
-        return process_vec(value);
-    } else if value.self_is_binary(){
-        return process_binary(value);
-    } else if value.self_is_num(){
-        return process_num(value);
-    } else if value.self_is_float(){
-        return process_float(value);
-    } else if value.self_is_string(){
-        return process_string(value);
-    } else if value.self_is_bool(){
-        return process_bool(value);
-    } else {
-        return process_null(value);
+///a document with its precomputed container sizes, ready to be written after the header
+#[cfg_attr(not(feature = "compression"), allow(dead_code))]
+struct Document<'d>{
+    doc:&'d DocValue,
+    layout:Layout,
+    flags:u8,
+    sizes:Vec<usize>,
+    root_len:usize,
+}
+
+impl<'d> Document<'d>{
+    fn write_body<W:Write>(self,out:&mut W)->io::Result<()>{
+        #[cfg(feature = "compression")]
+        if self.flags & crate::header::FLAG_COMPRESSED != 0{
+            let mut data = Vec::with_capacity(self.root_len);
+            let mut writer = Writer{out:&mut data,sizes:self.sizes,index:0,layout:self.layout};
+            writer.process_value(self.doc)?;
+            return crate::compress::write_block(out,&data);
+        }
+        let mut writer = Writer{out,sizes:self.sizes,index:0,layout:self.layout};
+        writer.process_value(self.doc)
     }
 }
 
 /*
 
+header - optional, magic("GZBD") version(u8) flags(u8) followed by the root data_line
+    version 1 - default without header, data_len_rep is a fixed u64
+    version 2 - data_len_rep is a varint and object and vec entries are not wrapped in a data_len_rep
+    flags - bit 0 counted, objects and vecs start with their entry count and have no continue bytes
+    flags - bit 1 checksum, the root data_line is followed by a crc32c(u32 big endien) of the header and root data_line
+    flags - bit 2 compressed, the root data_line is replaced by u64 data length, u64 block length and a lz4 block, the checksum covers the block
+
 data_len_rep - u64 num representing length of data in bytes represented in u64 bytes big endien
+    version 2 - unsigned LEB128 varint, 7 bits per byte low bits first, high bit set if more bytes follow
 
 continue_byte - if 1 next is key if 0 this is last key
 
@@ -47,6 +98,10 @@ data_line - data_type data_len_rep data(nested as per data parse)
 data_parse 
     object - **repeating pattern** data_len_rep key data_len_rep data continue_byte(if 1 next is key if 0 this is last key)
     vec - **repeating pattern** data_len_rep data continue_byte
+    version 2 object - **repeating pattern** data_len_rep key data_line continue_byte
+    version 2 vec - **repeating pattern** data_line continue_byte
+    counted object or vec - data_len_rep(entry count) then the entries without continue_byte, also for empty ones
+    empty object or vec - no data, data_len_rep is 0
     binary - data(vec<u8>)
     string - data(utf8 string as bytes)
     num - data(i64 num as bytes big endien)
@@ -55,81 +110,232 @@ data_parse
     null - data(0 as byte)  - 1 byte
 */
 
-pub fn process_object(object:&DocValue)->Vec<u8>{
-    let mut build = vec![];
-    let map = object.as_object().unwrap();
-    let map_len = map.len();
-    let mut index = 0;
-    for (key,value) in map.iter(){
-        let continue_byte:u8;
-        if index == map_len-1{continue_byte = 0;} else {continue_byte = 1;}
-        let mut key_data_len = data_len_rep(key.len() as u64);
-        let mut processed_data = process_value(value);
-        let mut processed_data_len = data_len_rep(processed_data.len() as u64);
-        build.append(&mut key_data_len);
-        build.append(&mut key.as_bytes().to_vec());
-        build.append(&mut processed_data_len);
-        build.append(&mut processed_data);
-        build.push(continue_byte);
-        index += 1;
-    }
-    data_line(0, build)
-}
-pub fn process_vec(object:&DocValue)->Vec<u8>{
-    let mut build = vec![];
-    let mut index = 0;
-    let pool = object.as_vec().unwrap();
-    let pool_len = pool.len();
-    for item in pool{
-        let continue_byte:u8;
-        if index == pool_len-1{continue_byte = 0;} else {continue_byte = 1;}
-        let mut processed_value = process_value(&item);
-        build.append(&mut data_len_rep(processed_value.len() as u64));
-        build.append(&mut processed_value);
-        build.push(continue_byte);
-        index += 1;
-    }
-    data_line(1, build)
+///
+/// streams the encoding of a document, every data line starts with the
+/// length of its data so the data length of each object and vec is
+/// computed first by line_len and consumed here in the same order.
+///
+struct Writer<'w,W:Write>{
+    out:&'w mut W,
+    sizes:Vec<usize>,
+    index:usize,
+    layout:Layout,
 }
-pub fn process_binary(object:&DocValue)->Vec<u8>{
-    data_line(2, object.as_binary().unwrap())
-}
-pub fn process_string(object:&DocValue)->Vec<u8>{
-    data_line(3, object.as_string().unwrap().as_bytes().to_vec())
+
+impl<'w,W:Write> Writer<'w,W>{
+    fn process_value(&mut self,value:&DocValue)->io::Result<()>{
+        match value{
+            DocValue::Object(map)=>{self.process_object(map)},
+            DocValue::Vec(pool)=>{self.process_vec(pool)},
+            DocValue::Binary(v)=>{self.data_line(2,v)},
+            DocValue::String(v)=>{self.data_line(3,v.as_bytes())},
+            DocValue::Num(v)=>{self.data_line(4,&v.to_be_bytes())},
+            DocValue::Float(v)=>{self.data_line(5,&self.layout.float(*v).to_be_bytes())},
+            DocValue::Bool(v)=>{self.data_line(6,&[*v as u8])},
+            DocValue::Null=>{self.data_line(7,&[0])},
+        }
+    }
+    fn process_object(&mut self,map:&Map<String,DocValue>)->io::Result<()>{
+        self.line_head(0,self.sizes[self.index])?;
+        self.index += 1;
+        if self.layout.counted{
+            self.data_len_rep(map.len())?;
+        }
+        let map_len = map.len();
+        for (index,(key,value)) in self.layout.entries(map).enumerate(){
+            self.data_len_rep(key.len())?;
+            self.out.write_all(key.as_bytes())?;
+            self.process_entry(value)?;
+            self.continue_byte(index + 1 != map_len)?;
+        }
+        Ok(())
+    }
+    fn process_vec(&mut self,pool:&[DocValue])->io::Result<()>{
+        self.line_head(1,self.sizes[self.index])?;
+        self.index += 1;
+        if self.layout.counted{
+            self.data_len_rep(pool.len())?;
+        }
+        let pool_len = pool.len();
+        for (index,item) in pool.iter().enumerate(){
+            self.process_entry(item)?;
+            self.continue_byte(index + 1 != pool_len)?;
+        }
+        Ok(())
+    }
+    ///value of a object or vec entry, version 1 wraps it in a data_len_rep
+    fn process_entry(&mut self,value:&DocValue)->io::Result<()>{
+        if self.layout.version == 1{
+            let line_len = match value{
+                DocValue::Object(_) | DocValue::Vec(_)=>{self.layout.head_len(self.sizes[self.index]) + self.sizes[self.index]},
+                _=>{self.layout.head_len(scalar_len(value)) + scalar_len(value)},
+            };
+            self.data_len_rep(line_len)?;
+        }
+        self.process_value(value)
+    }
+    fn continue_byte(&mut self,more:bool)->io::Result<()>{
+        if self.layout.counted{
+            return Ok(());
+        }
+        self.out.write_all(&[more as u8])
+    }
+    //data builders
+    fn data_line(&mut self,data_type:u8,data:&[u8])->io::Result<()>{
+        self.line_head(data_type,data.len())?;
+        self.out.write_all(data)
+    }
+    fn line_head(&mut self,data_type:u8,len:usize)->io::Result<()>{
+        self.out.write_all(&[data_type])?;
+        self.data_len_rep(len)
+    }
+    fn data_len_rep(&mut self,v:usize)->io::Result<()>{
+        if self.layout.version == 1{
+            return self.out.write_u64::<BigEndian>(v as u64);
+        }
+        let mut v = v as u64;
+        let mut build = [0u8;10];
+        let mut size = 0;
+        loop{
+            let byte = (v & 0x7f) as u8;
+            v >>= 7;
+            if v == 0{
+                build[size] = byte;
+                size += 1;
+                break;
+            }
+            build[size] = byte | 0x80;
+            size += 1;
+        }
+        self.out.write_all(&build[..size])
+    }
 }
-pub fn process_num(object:&DocValue)->Vec<u8>{
-    data_line(4, num_to_data(object.as_num().unwrap()))
+
+///format version and flags that change how values are laid out
+#[derive(Clone,Copy)]
+struct Layout{
+    version:u8,
+    counted:bool,
+    canonical:bool,
 }
-pub fn process_float(object:&DocValue)->Vec<u8>{
-    data_line(5, float_to_data(object.as_float().unwrap()))
+
+impl Layout{
+    ///object entries in the order they are written, sorted by key bytes if canonical
+    fn entries<'m>(&self,map:&'m Map<String,DocValue>)->Entries<'m>{
+        if !self.canonical{
+            return Entries::Map(map.iter());
+        }
+        let mut sorted:Vec<(&String,&DocValue)> = map.iter().collect();
+        sorted.sort_unstable_by(|a,b|a.0.as_bytes().cmp(b.0.as_bytes()));
+        Entries::Sorted(sorted.into_iter())
+    }
+    ///float as it is written, canonical turns -0.0 into 0.0 and every nan into one quiet nan
+    fn float(&self,v:f64)->f64{
+        if !self.canonical{
+            return v;
+        }
+        canonical_float(v)
+    }
+    ///length of a data_len_rep holding v
+    fn len_rep_len(&self,v:usize)->usize{
+        if self.version == 1{
+            return 8;
+        }
+        let mut size = 1;
+        let mut v = v as u64 >> 7;
+        while v != 0{
+            size += 1;
+            v >>= 7;
+        }
+        size
+    }
+    ///length of data_type and data_len_rep of a data line with len bytes of data
+    fn head_len(&self,len:usize)->usize{
+        1 + self.len_rep_len(len)
+    }
+    ///length of a object or vec entry value with its continue byte
+    fn entry_len(&self,line_len:usize)->usize{
+        let mut len = line_len;
+        if self.version == 1{
+            len += self.len_rep_len(line_len);
+        }
+        if !self.counted{
+            len += 1;
+        }
+        len
+    }
+    ///length of the entry count of a object or vec
+    fn count_len(&self,count:usize)->usize{
+        if self.counted{
+            return self.len_rep_len(count);
+        }
+        0
+    }
+    ///
+    /// length of the data line of value, the data length of every object and
+    /// vec is pushed to sizes in the order the writer visits them.
+    ///
+    fn line_len(&self,value:&DocValue,sizes:&mut Vec<usize>)->usize{
+        match value{
+            DocValue::Object(map)=>{
+                let slot = sizes.len();
+                sizes.push(0);
+                let mut len = self.count_len(map.len());
+                for (key,value) in self.entries(map){
+                    len += self.len_rep_len(key.len()) + key.len() + self.entry_len(self.line_len(value,sizes));
+                }
+                sizes[slot] = len;
+                self.head_len(len) + len
+            },
+            DocValue::Vec(pool)=>{
+                let slot = sizes.len();
+                sizes.push(0);
+                let mut len = self.count_len(pool.len());
+                for item in pool{
+                    len += self.entry_len(self.line_len(item,sizes));
+                }
+                sizes[slot] = len;
+                self.head_len(len) + len
+            },
+            _=>{self.head_len(scalar_len(value)) + scalar_len(value)}
+        }
+    }
 }
-pub fn process_bool(object:&DocValue)->Vec<u8>{
-    let data:Vec<u8>;
-    if object.as_bool().unwrap() {data = vec![1];} else {data = vec![0];};
-    data_line(6, data)
+
+///bits of the nan written by canonical encoding
+const CANONICAL_NAN:u64 = 0x7ff8_0000_0000_0000;
+
+///-0.0 as 0.0 and every nan as CANONICAL_NAN, also used to compare and hash floats
+pub fn canonical_float(v:f64)->f64{
+    if v.is_nan(){
+        return f64::from_bits(CANONICAL_NAN);
+    }
+    if v == 0.0{
+        return 0.0;
+    }
+    v
 }
-pub fn process_null(_object:&DocValue)->Vec<u8>{data_line(7, vec![0])}
-
-//data builders
-fn data_line(data_type:u8,mut data:Vec<u8>)->Vec<u8>{
-    let mut data_len = data_len_rep(data.len() as u64);
-    let mut build = vec![data_type];
-    build.append(&mut data_len);
-    build.append(&mut data);
-    build
+
+enum Entries<'m>{
+    Map(MapIter<'m,String,DocValue>),
+    Sorted(std::vec::IntoIter<(&'m String,&'m DocValue)>),
 }
-pub fn data_len_rep(v:u64)->Vec<u8>{
-    let mut wtr = vec![];
-    wtr.write_u64::<BigEndian>(v).unwrap();
-    wtr
+
+impl<'m> Iterator for Entries<'m>{
+    type Item = (&'m String,&'m DocValue);
+    fn next(&mut self)->Option<Self::Item>{
+        match self{
+            Entries::Map(iter)=>{iter.next()},
+            Entries::Sorted(iter)=>{iter.next()},
+        }
+    }
 }
-fn num_to_data(v:i64)->Vec<u8>{
-    let mut wtr = vec![];
-    wtr.write_i64::<BigEndian>(v).unwrap();
-    wtr
+
+fn scalar_len(value:&DocValue)->usize{
+    match value{
+        DocValue::Binary(v)=>{v.len()},
+        DocValue::String(v)=>{v.len()},
+        DocValue::Num(_) | DocValue::Float(_)=>{8},
+        _=>{1},
+    }
 }
-fn float_to_data(v:f64)->Vec<u8>{
-    let mut wtr = vec![];
-    wtr.write_f64::<BigEndian>(v).unwrap();
-    wtr
-}
\ No newline at end of file
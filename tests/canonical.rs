@@ -0,0 +1,68 @@
+use gzbbinarydoc::{doc,is_canonical,DocValue,EncodeOptions,Format};
+
+fn sample()->DocValue{
+    doc!({
+        "zebra":1,
+        "apple":{"b":-0.0,"a":f64::NAN,"é":2,"z":3},
+        "mango":[{"y":null,"x":bin[1]},f64::from_bits(0x7ff8_0000_0000_0001)],
+        "Apple":true,
+    })
+}
+
+#[test]
+fn canonical_bytes_are_stable(){
+    let bin = sample().write_canonical();
+    for _ in 0..8{
+        let rebuilt = DocValue::read(&bin).unwrap();
+        assert_eq!(rebuilt.write_canonical(),bin);
+        assert_eq!(sample().write_canonical(),bin);
+    }
+    //keys sorted by bytes, "A" before "a" and "z" before "é"
+    let keys = ["Apple","apple","mango","zebra"];
+    let mut last = 0;
+    for key in keys{
+        let at = bin.windows(key.len()).position(|w|w == key.as_bytes()).unwrap();
+        assert!(at > last);
+        last = at;
+    }
+    let z = bin.windows(1).position(|w|w == b"z").unwrap();
+    let e = bin.windows(2).position(|w|w == "é".as_bytes()).unwrap();
+    assert!(z < e);
+}
+
+#[test]
+fn canonical_floats(){
+    let bin = doc!([-0.0,f64::from_bits(0xfff8_0000_0000_00ff)]).write_canonical();
+    let back = DocValue::read(&bin).unwrap();
+    let pool = back.as_vec().unwrap();
+    assert_eq!(pool[0].as_float().unwrap().to_bits(),0);
+    assert_eq!(pool[1].as_float().unwrap().to_bits(),0x7ff8_0000_0000_0000);
+    let plain = doc!([-0.0]).write();
+    assert_eq!(DocValue::read(&plain).unwrap().as_vec().unwrap()[0].as_float().unwrap().to_bits(),(-0.0f64).to_bits());
+    assert!(!is_canonical(&plain).unwrap());
+}
+
+#[test]
+fn checks_canonical_input(){
+    for format in [Format::V1,Format::V2]{
+        for counted in [false,true]{
+            for checksum in [false,true]{
+                let options = EncodeOptions{format,counted,checksum,canonical:true,..EncodeOptions::default()};
+                let bin = sample().write_with(options);
+                assert!(is_canonical(&bin).unwrap());
+            }
+        }
+    }
+    assert!(!is_canonical(&sample().write_with_header()).unwrap());
+    //two keys written in reverse order
+    let doc = doc!({"a":1,"b":2});
+    let mut bin = doc.write_canonical();
+    assert!(is_canonical(&bin).unwrap());
+    let a = bin.windows(1).position(|w|w == b"a").unwrap();
+    let b = bin.windows(1).position(|w|w == b"b").unwrap();
+    bin.swap(a,b);
+    let swapped = DocValue::read(&bin).unwrap();
+    assert!(swapped.as_object().unwrap()["a"].as_num().unwrap() == 2);
+    assert!(!is_canonical(&bin).unwrap());
+    assert!(is_canonical(&[9]).is_err());
+}
@@ -0,0 +1,59 @@
+use gzbbinarydoc::{DocValue,DecodeError,EncodeOptions,Format,get_path,verify};
+use std::io::Cursor;
+
+mod common;
+use common::person;
+
+fn checksum(format:Format)->EncodeOptions{
+    EncodeOptions{format,checksum:true,..EncodeOptions::default()}
+}
+
+#[test]
+fn checksum_trailer(){
+
+    // v2 vec holding true, header with flag bit 1 then crc32c of all bytes before it
+    let mut doc = DocValue::vec();
+    doc.push(true);
+    let bin = doc.write_with(checksum(Format::V2));
+    assert_eq!(&bin[..6],b"GZBD\x02\x02");
+    assert_eq!(&bin[6..12],&[1,4,6,1,1,0]);
+    assert_eq!(&bin[12..],&0xee72_9a99u32.to_be_bytes());
+
+    let mut out = vec![];
+    doc.write_to_with(&mut out,checksum(Format::V2)).unwrap();
+    assert_eq!(out,bin);
+
+}
+
+#[test]
+fn checksum_round_trip(){
+    for format in [Format::V1,Format::V2]{
+        let bin = person().write_with(checksum(format));
+        assert!(verify(&bin).unwrap());
+        let doc = DocValue::read(&bin).unwrap();
+        assert!(doc.key_is_string("name"));
+        let score = get_path(&bin,&["sports","match","scores","1"]).unwrap().unwrap();
+        assert_eq!(score.as_num().unwrap(),15);
+        let mut stream = Cursor::new(bin.repeat(2));
+        DocValue::read_from(&mut stream).unwrap();
+        DocValue::read_from(&mut stream).unwrap();
+        assert!(matches!(DocValue::read_from(&mut stream),Err(DecodeError::Eof)));
+    }
+    assert!(!verify(&person().write()).unwrap());
+}
+
+#[test]
+fn bit_flips_are_detected(){
+    let bin = person().write_with(checksum(Format::V2));
+    for index in 6..bin.len(){
+        for bit in 0..8{
+            let mut bin = bin.clone();
+            bin[index] ^= 1 << bit;
+            assert!(matches!(verify(&bin),Err(DecodeError::ChecksumMismatch{..})));
+            assert!(matches!(DocValue::read(&bin),Err(DecodeError::ChecksumMismatch{..})));
+        }
+    }
+    for len in 0..bin.len(){
+        assert!(DocValue::read(&bin[..len]).is_err());
+    }
+}
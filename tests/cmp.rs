@@ -0,0 +1,51 @@
+use gzbbinarydoc::{doc,DocValue};
+use std::collections::{BTreeSet,HashSet};
+
+#[test]
+fn documents_compare_equal(){
+    let a = doc!({"name":"akku","scores":[12.5,15,null],"bin":bin[1,2],"meta":{"x":1,"y":2}});
+    let mut b = DocValue::object();
+    b.insert("meta",doc!({"y":2,"x":1}));
+    b.insert("bin",vec![1u8,2]);
+    b.insert("scores",doc!([12.5,15,null]));
+    b.insert("name","akku");
+    assert_eq!(a,b);
+    assert_eq!(DocValue::read(&a.write()).unwrap(),a);
+    b.insert("name","king");
+    assert_ne!(a,b);
+    assert_ne!(doc!(1),doc!(1.0));
+    assert_ne!(doc!(bin[1]),doc!([1]));
+    assert_ne!(doc!({"a":null}),doc!({"b":null}));
+}
+
+#[test]
+fn floats_are_deterministic(){
+    assert_eq!(doc!(-0.0),doc!(0.0));
+    assert_eq!(doc!(f64::NAN),doc!(f64::from_bits(0xfff8_0000_0000_0001)));
+    assert!(doc!(f64::NAN) > doc!(f64::INFINITY));
+    assert!(doc!(f64::NEG_INFINITY) < doc!(-1e300));
+    let mut set = HashSet::new();
+    set.insert(doc!(-0.0));
+    set.insert(doc!(0.0));
+    set.insert(doc!(f64::NAN));
+    set.insert(doc!(-f64::NAN));
+    assert_eq!(set.len(),2);
+}
+
+#[test]
+fn total_order_across_variants(){
+    let mut pool = vec![
+        doc!({"b":1}),doc!({"a":2}),doc!({"a":1,"b":0}),doc!([2]),doc!([1,5]),doc!([]),
+        doc!(bin[0]),doc!("b"),doc!("a"),doc!(2.5),doc!(1),doc!(-3),doc!(true),doc!(false),doc!(null),
+    ];
+    pool.sort();
+    let expected = vec![
+        doc!(null),doc!(false),doc!(true),doc!(-3),doc!(1),doc!(2.5),doc!("a"),doc!("b"),doc!(bin[0]),
+        doc!([]),doc!([1,5]),doc!([2]),doc!({"a":1,"b":0}),doc!({"a":2}),doc!({"b":1}),
+    ];
+    assert_eq!(pool,expected);
+    let keys:BTreeSet<DocValue> = pool.iter().cloned().chain(expected).collect();
+    assert_eq!(keys.len(),15);
+    let hashed:HashSet<DocValue> = pool.into_iter().chain([doc!({"b":0,"a":1})]).collect();
+    assert_eq!(hashed.len(),15);
+}
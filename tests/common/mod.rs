@@ -0,0 +1,35 @@
+use gzbbinarydoc::DocValue;
+
+///
+/// the person document of the README, every type and two levels of
+/// nesting. its encoded size is in the README so keep the two in sync.
+///
+pub fn person()->DocValue{
+
+    let mut person = DocValue::object();
+
+    person.insert("name","akku");
+    person.insert("bin",vec![1u8,2,3]);
+    person.insert("age",24);
+    person.insert("avg score",12.65);
+    person.insert("king",true);
+    person.insert("network",());
+
+    let mut scores = DocValue::vec();
+    scores.push(12.5);
+    scores.push(15);
+    scores.push(());
+    scores.push(false);
+
+    let mut game_match = DocValue::object();
+    game_match.insert("scores",scores);
+
+    let mut game = DocValue::object();
+    game.insert("match", game_match);
+    game.insert("game","cricket");
+
+    person.insert("sports",game);
+
+    person
+
+}
@@ -0,0 +1,123 @@
+use gzbbinarydoc::{DocValue,DecodeError};
+
+fn players(count:usize)->DocValue{
+    let mut players = DocValue::vec();
+    for index in 0..count{
+        let mut player = DocValue::object();
+        player.insert("name","akku");
+        player.insert("game","cricket");
+        player.insert("score",index as i64);
+        players.push(player);
+    }
+    players
+}
+
+#[cfg(feature = "compression")]
+mod compressed{
+
+    use super::*;
+    use gzbbinarydoc::{DocRef,EncodeOptions,Format,get_path,get_path_bytes,get_path_len,get_path_range,is_canonical,verify};
+    use std::io::Cursor;
+
+    #[test]
+    fn compressed_round_trip(){
+        let doc = players(100);
+        let bin = doc.write_compressed();
+        assert_eq!(&bin[..6],b"GZBD\x01\x04");
+        assert!(bin.len() * 4 < doc.write().len());
+        let rebuild = DocValue::read(&bin).unwrap();
+        assert_eq!(rebuild.as_vec().unwrap().len(),100);
+        let score = get_path(&bin,&["42","score"]).unwrap().unwrap();
+        assert_eq!(score.as_num().unwrap(),42);
+        assert_eq!(get_path_len(&bin,&[]).unwrap(),Some(100));
+        assert!(matches!(DocRef::read(&bin),Err(DecodeError::Compressed{..})));
+        assert!(matches!(get_path_range(&bin,&["0"]),Err(DecodeError::Compressed{..})));
+        let player = get_path_bytes(&bin,&["42"]).unwrap().unwrap();
+        assert_eq!(DocValue::read(&player).unwrap().as_object().unwrap().get("score").unwrap().as_num().unwrap(),42);
+        let mut stream = Cursor::new(bin.repeat(2));
+        DocValue::read_from(&mut stream).unwrap();
+        DocValue::read_from(&mut stream).unwrap();
+        assert!(matches!(DocValue::read_from(&mut stream),Err(DecodeError::Eof)));
+    }
+
+    #[test]
+    fn small_documents_stay_uncompressed(){
+        let doc = players(1);
+        assert_eq!(doc.write_compressed(),doc.write());
+        let options = EncodeOptions{format:Format::V2,compression:Some(0),..EncodeOptions::default()};
+        let bin = doc.write_with(options);
+        assert_eq!(bin[5],4);
+        assert!(DocValue::read(&bin).unwrap().self_is_vec());
+    }
+
+    #[test]
+    fn compressed_canonical(){
+        let options = EncodeOptions{canonical:true,compression:Some(0),..EncodeOptions::default()};
+        let bin = players(50).write_with(options);
+        assert_eq!(bin[5],4);
+        assert!(is_canonical(&bin).unwrap());
+        assert_eq!(DocValue::read(&bin).unwrap().write_with(options),bin);
+    }
+
+    #[test]
+    fn compressed_with_checksum(){
+        let options = EncodeOptions{format:Format::V2,counted:true,checksum:true,compression:Some(0),..EncodeOptions::default()};
+        let bin = players(20).write_with(options);
+        assert_eq!(bin[5],7);
+        assert!(verify(&bin).unwrap());
+        assert_eq!(DocValue::read(&bin).unwrap().as_vec().unwrap().len(),20);
+        for index in 6..bin.len(){
+            let mut bin = bin.clone();
+            bin[index] ^= 0x10;
+            assert!(matches!(DocValue::read(&bin),Err(DecodeError::ChecksumMismatch{..})));
+        }
+    }
+
+    #[test]
+    fn corrupt_blocks_are_errors(){
+        let bin = players(50).write_compressed();
+        for len in 0..bin.len(){
+            assert!(DocValue::read(&bin[..len]).is_err());
+        }
+        for index in 6..bin.len(){
+            let mut bin = bin.clone();
+            bin[index] ^= 0xff;
+            let _ = DocValue::read(&bin);
+        }
+        let mut bin = bin.clone();
+        bin[6..14].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert!(matches!(DocValue::read(&bin),Err(DecodeError::LimitExceeded{..})));
+    }
+
+    #[test]
+    fn tiny_block_with_huge_length_is_rejected(){
+        let mut bin = b"GZBD\x01\x04".to_vec();
+        bin.extend_from_slice(&(1000u64 * 1024 * 1024).to_be_bytes());
+        bin.extend_from_slice(&1u64.to_be_bytes());
+        bin.push(0);
+        assert!(matches!(DocValue::read(&bin),Err(DecodeError::InvalidCompressed{offset:6})));
+        assert!(matches!(get_path(&bin,&["0"]),Err(DecodeError::InvalidCompressed{..})));
+        assert!(matches!(DocValue::read_from(&mut Cursor::new(&bin)),Err(DecodeError::InvalidCompressed{..})));
+    }
+
+}
+
+#[cfg(not(feature = "compression"))]
+#[test]
+fn compressed_needs_feature(){
+    let mut bin = b"GZBD\x01\x04".to_vec();
+    bin.extend_from_slice(&players(1).write());
+    assert!(matches!(DocValue::read(&bin),Err(DecodeError::UnsupportedFlags{flags:4,..})));
+}
+
+#[test]
+fn compression_option_exists_without_feature(){
+    let doc = players(100);
+    let options = gzbbinarydoc::EncodeOptions{format:gzbbinarydoc::Format::V2,header:true,counted:false,checksum:false,compression:Some(0),canonical:false};
+    let bin = doc.write_with(options);
+    assert_eq!(DocValue::read(&bin).unwrap().as_vec().unwrap().len(),100);
+    #[cfg(feature = "compression")]
+    assert_eq!(bin[5],4);
+    #[cfg(not(feature = "compression"))]
+    assert_eq!(bin,doc.write_with(gzbbinarydoc::EncodeOptions{compression:None,..options}));
+}
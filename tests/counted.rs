@@ -0,0 +1,67 @@
+use gzbbinarydoc::{DocValue,DecodeError,EncodeOptions,Format,get_path,get_path_len};
+use std::io::Cursor;
+
+mod common;
+use common::person;
+
+fn sample()->DocValue{
+    let mut doc = person();
+    doc.insert("tags",DocValue::vec());
+    doc.insert("meta",DocValue::object());
+    doc
+}
+
+fn counted(format:Format)->EncodeOptions{
+    EncodeOptions{format,counted:true,..EncodeOptions::default()}
+}
+
+#[test]
+fn counted_round_trip(){
+    for format in [Format::V1,Format::V2]{
+        let bin = sample().write_with(counted(format));
+        assert_eq!(bin[5],1);
+        let doc = DocValue::read(&bin).unwrap();
+        assert_eq!(doc.as_object().unwrap().len(),9);
+        let sports = doc.as_object().unwrap().get("sports").unwrap().clone();
+        let game_match = sports.as_object().unwrap().get("match").unwrap().clone();
+        assert_eq!(game_match.as_object().unwrap().get("scores").unwrap().as_vec().unwrap().len(),4);
+        assert_eq!(doc.as_object().unwrap().get("tags").unwrap().as_vec().unwrap().len(),0);
+        assert_eq!(doc.as_object().unwrap().get("meta").unwrap().as_object().unwrap().len(),0);
+        let score = get_path(&bin,&["sports","match","scores","1"]).unwrap().unwrap();
+        assert_eq!(score.as_num().unwrap(),15);
+        let streamed = DocValue::read_from(&mut Cursor::new(&bin)).unwrap();
+        assert!(streamed.key_is_object("sports"));
+        for len in 0..bin.len(){
+            assert!(DocValue::read(&bin[..len]).is_err());
+        }
+    }
+}
+
+#[test]
+fn path_len(){
+    for options in [EncodeOptions::default(),counted(Format::V1),counted(Format::V2)]{
+        let bin = sample().write_with(options);
+        assert_eq!(get_path_len(&bin,&[]).unwrap(),Some(9));
+        assert_eq!(get_path_len(&bin,&["sports","match","scores"]).unwrap(),Some(4));
+        assert_eq!(get_path_len(&bin,&["tags"]).unwrap(),Some(0));
+        assert_eq!(get_path_len(&bin,&["name"]).unwrap(),None);
+        assert_eq!(get_path_len(&bin,&["missing"]).unwrap(),None);
+    }
+}
+
+#[test]
+fn count_mismatch(){
+
+    // counted v2 vec holding true, the count byte is at index 8
+    let mut doc = DocValue::vec();
+    doc.push(true);
+    let bin = doc.write_with(counted(Format::V2));
+    assert_eq!(&bin[6..],&[1,4,1,6,1,1]);
+
+    for count in [0,2]{
+        let mut bin = bin.clone();
+        bin[8] = count;
+        assert!(matches!(DocValue::read(&bin),Err(DecodeError::CountMismatch{..})));
+    }
+
+}
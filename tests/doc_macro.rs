@@ -0,0 +1,45 @@
+use gzbbinarydoc::{doc,DocValue,FromDocValue};
+use std::collections::HashMap;
+
+#[test]
+fn builds_nested_documents(){
+    let age = 24u8;
+    let key = "level";
+    let person = doc!({
+        "name":"akku",
+        "bin":bin[1,2,3],
+        "age":age,
+        "avg score":12.65,
+        "king":true,
+        "network":null,
+        key:-3,
+        "sports":{
+            "game":"cricket",
+            "match":{"scores":[12.5,15,null,false,bin[],[],{}]},
+        },
+    });
+    let map = HashMap::<String,DocValue>::fromDocValue(&person).unwrap();
+    assert_eq!(map.len(),8);
+    assert_eq!(map["bin"].as_binary().unwrap(),vec![1,2,3]);
+    assert_eq!(map["age"].as_num().unwrap(),24);
+    assert_eq!(map["level"].as_num().unwrap(),-3);
+    assert!(matches!(map["network"],DocValue::Null));
+    let scores = map["sports"].as_object().unwrap()["match"].as_object().unwrap()["scores"].as_vec().unwrap();
+    assert_eq!(scores.len(),7);
+    assert_eq!(scores[0].as_float().unwrap(),12.5);
+    assert_eq!(scores[1].as_num().unwrap(),15);
+    assert!(matches!(scores[2],DocValue::Null));
+    assert!(matches!(scores[3],DocValue::Bool(false)));
+    assert!(matches!(&scores[4],DocValue::Binary(b) if b.is_empty()));
+    assert!(matches!(&scores[5],DocValue::Vec(v) if v.is_empty()));
+    assert!(matches!(&scores[6],DocValue::Object(o) if o.is_empty()));
+    assert_eq!(DocValue::read(&person.write()).unwrap().write().len(),person.write().len());
+}
+
+#[test]
+fn single_values(){
+    assert!(matches!(doc!(null),DocValue::Null));
+    assert!(matches!(doc!(vec![1u8]),DocValue::Binary(_)));
+    assert!(matches!(doc!([1u8]),DocValue::Vec(_)));
+    assert!(matches!(doc!(2 + 3),DocValue::Num(5)));
+}
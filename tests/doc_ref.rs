@@ -0,0 +1,38 @@
+use gzbbinarydoc::{DocValue,DocRef};
+
+#[test]
+fn borrows_from_input(){
+
+    let mut doc = DocValue::object();
+    doc.insert("name","akku");
+    doc.insert("bin",vec![1u8,2,3]);
+    doc.insert("score",12.65);
+    let bin = doc.write();
+
+    let view = DocRef::read(&bin).unwrap();
+    let range = bin.as_ptr_range();
+
+    let name = view.get("name").unwrap().as_string().unwrap();
+    assert_eq!(name,"akku");
+    assert!(range.contains(&name.as_ptr()));
+
+    let binary = view.get("bin").unwrap().as_binary().unwrap();
+    assert_eq!(binary,&[1,2,3]);
+    assert!(range.contains(&binary.as_ptr()));
+
+    assert!(view.key_is_float("score"));
+    assert_eq!(view.as_object().unwrap().len(),3);
+
+}
+
+#[test]
+fn to_owned_matches_read(){
+    let mut scores = DocValue::vec();
+    scores.push(12.5);
+    scores.push(());
+    let mut doc = DocValue::object();
+    doc.insert("scores",scores);
+    let bin = doc.write();
+    let owned = DocRef::read(&bin).unwrap().to_owned();
+    assert_eq!(owned.write(),DocValue::read(&bin).unwrap().write());
+}
@@ -0,0 +1,52 @@
+use gzbbinarydoc::DocValue;
+
+fn rebuild(doc:&DocValue)->DocValue{
+    DocValue::read(&doc.write()).unwrap()
+}
+
+#[test]
+fn empty_object_root(){
+    let doc = rebuild(&DocValue::object());
+    assert_eq!(doc.as_object().unwrap().len(),0);
+}
+
+#[test]
+fn empty_vec_root(){
+    let doc = rebuild(&DocValue::vec());
+    assert_eq!(doc.as_vec().unwrap().len(),0);
+}
+
+#[test]
+fn empty_containers_nested(){
+
+    let mut tags = DocValue::vec();
+    tags.push(DocValue::vec());
+    tags.push(DocValue::object());
+
+    let mut doc = DocValue::object();
+    doc.insert("tags",tags);
+    doc.insert("meta",DocValue::object());
+    doc.insert("list",DocValue::vec());
+    doc.insert("name","akku");
+
+    let doc = rebuild(&doc);
+
+    let tags = doc.as_object().unwrap().get("tags").unwrap().as_vec().unwrap();
+    assert_eq!(tags.len(),2);
+    assert_eq!(tags[0].as_vec().unwrap().len(),0);
+    assert_eq!(tags[1].as_object().unwrap().len(),0);
+    assert!(doc.key_is_object("meta"));
+    assert!(doc.key_is_vec("list"));
+    assert_eq!(doc.as_object().unwrap().get("name").unwrap().as_string().unwrap(),"akku");
+
+}
+
+#[test]
+fn empty_string_and_binary(){
+    let mut doc = DocValue::vec();
+    doc.push("");
+    doc.push(DocValue::binary());
+    let doc = rebuild(&doc).as_vec().unwrap();
+    assert_eq!(doc[0].as_string().unwrap(),"");
+    assert_eq!(doc[1].as_binary().unwrap().len(),0);
+}
@@ -0,0 +1,46 @@
+use gzbbinarydoc::{DocValue,EncodeOptions,Format,get_path};
+use std::io::Cursor;
+
+mod common;
+use common::person;
+
+fn v2()->EncodeOptions{
+    EncodeOptions{format:Format::V2,..EncodeOptions::default()}
+}
+
+#[test]
+fn v2_round_trip(){
+    let bin = person().write_with(v2());
+    assert_eq!(&bin[..5],b"GZBD\x02");
+    let rebuild = DocValue::read(&bin).unwrap();
+    let sports = rebuild.as_object().unwrap().get("sports").unwrap().clone();
+    assert_eq!(sports.as_object().unwrap().get("game").unwrap().as_string().unwrap(),"cricket");
+    let score = get_path(&bin,&["sports","match","scores","0"]).unwrap().unwrap();
+    assert_eq!(score.as_float().unwrap(),12.5);
+    let streamed = DocValue::read_from(&mut Cursor::new(&bin)).unwrap();
+    assert!(streamed.key_is_object("sports"));
+    let mut out = vec![];
+    person().write_to_with(&mut out,v2()).unwrap();
+    assert_eq!(out.len(),bin.len());
+}
+
+#[test]
+fn v2_size_reduction(){
+    assert_eq!(person().write().len(),442);
+    assert_eq!(person().write_with(v2()).len(),162);
+    let mut doc = DocValue::object();
+    doc.insert("k",true);
+    assert_eq!(doc.write().len(),37);
+    assert_eq!(doc.write_with(v2()).len(),14);
+}
+
+#[test]
+fn v2_truncated_and_overlong_lengths(){
+    let bin = person().write_with(v2());
+    for len in 0..bin.len(){
+        assert!(DocValue::read(&bin[..len]).is_err());
+    }
+    let mut bin = b"GZBD\x02\x00\x03".to_vec();
+    bin.extend_from_slice(&[0xff;11]);
+    assert!(DocValue::read(&bin).is_err());
+}
@@ -0,0 +1,53 @@
+use gzbbinarydoc::{DocValue,FromDocValue,FromDocError};
+use std::collections::{BTreeMap,HashMap};
+
+mod common;
+use common::person;
+
+///the shared person with a float vec and a pair at the root
+fn sample()->DocValue{
+    let mut scores = DocValue::vec();
+    scores.push(12.5);
+    scores.push(15.0);
+    let mut pair = DocValue::vec();
+    pair.push("akku");
+    pair.push(300);
+    let mut doc = person();
+    doc.insert("scores",scores);
+    doc.insert("pair",pair);
+    doc
+}
+
+#[test]
+fn typed_extraction(){
+    let doc = sample();
+    let map:HashMap<String,DocValue> = HashMap::fromDocValue(&doc).unwrap();
+    let scores:Vec<f64> = Vec::fromDocValue(&map["scores"]).unwrap();
+    assert_eq!(scores,vec![12.5,15.0]);
+    let pair:(String,u16) = (&map["pair"]).try_into().unwrap();
+    assert_eq!(pair,("akku".to_string(),300));
+    assert_eq!(Vec::<u8>::try_from(&map["bin"]).unwrap(),vec![1,2,3]);
+    assert_eq!(Option::<i32>::fromDocValue(&map["network"]).unwrap(),None);
+    assert_eq!(Option::<String>::fromDocValue(&map["name"]).unwrap(),Some("akku".to_string()));
+    let mut only = DocValue::object();
+    only.insert("scores",map["scores"].clone());
+    let tree:BTreeMap<String,Vec<f32>> = BTreeMap::fromDocValue(&only).unwrap();
+    assert_eq!(tree["scores"],vec![12.5f32,15.0]);
+}
+
+#[test]
+fn errors_name_path_and_types(){
+    let doc = sample();
+    let err = String::try_from(&doc).unwrap_err();
+    assert_eq!((err.expected(),err.found(),err.path()),("string","object",""));
+    let mut outer = DocValue::object();
+    outer.insert("pair",sample().as_object().unwrap().get("pair").unwrap().clone());
+    let err = BTreeMap::<String,(String,u8)>::fromDocValue(&outer).unwrap_err();
+    assert_eq!(err,FromDocError::new("u8","num 300").with_index(1).with_key("pair"));
+    assert_eq!(err.path(),"pair[1]");
+    assert_eq!(err.to_string(),"expected u8 found num 300 (path: pair[1])");
+    let err = Vec::<u8>::fromDocValue(&DocValue::vec()).unwrap_err();
+    assert_eq!((err.expected(),err.found()),("binary","vec"));
+    let err = <(i64,i64)>::fromDocValue(&DocValue::vec()).unwrap_err();
+    assert_eq!((err.expected(),err.found()),("vec of 2","vec of 0"));
+}
@@ -0,0 +1,58 @@
+use gzbbinarydoc::{DocRef,DocValue,EncodeOptions,Format,get_path,get_path_bytes,get_path_range};
+
+mod common;
+use common::person;
+
+#[test]
+fn finds_nested_values(){
+    let bin = person().write();
+    let game = get_path(&bin,&["sports","game"]).unwrap().unwrap();
+    assert_eq!(game.as_string().unwrap(),"cricket");
+    let score = get_path(&bin,&["sports","match","scores","1"]).unwrap().unwrap();
+    assert_eq!(score.as_num().unwrap(),15);
+    let root = get_path(&bin,&[]).unwrap().unwrap();
+    assert!(root.key_is_object("sports"));
+}
+
+#[test]
+fn missing_paths(){
+    let bin = person().write();
+    assert!(get_path(&bin,&["sports","missing"]).unwrap().is_none());
+    assert!(get_path(&bin,&["sports","match","scores","4"]).unwrap().is_none());
+    assert!(get_path(&bin,&["sports","match","scores","x"]).unwrap().is_none());
+    assert!(get_path(&bin,&["name","first"]).unwrap().is_none());
+}
+
+#[test]
+fn range_decodes_on_its_own(){
+    let bin = person().write();
+    let range = get_path_range(&bin,&["sports","match"]).unwrap().unwrap();
+    let game_match = DocValue::read(&bin[range]).unwrap();
+    assert_eq!(game_match.as_object().unwrap().get("scores").unwrap().as_vec().unwrap().len(),4);
+}
+
+#[test]
+fn bytes_decode_on_their_own_in_every_format(){
+    let doc = person();
+    let bin = doc.write();
+    assert_eq!(get_path_bytes(&bin,&["sports","match"]).unwrap().unwrap(),&bin[get_path_range(&bin,&["sports","match"]).unwrap().unwrap()]);
+    assert!(get_path_bytes(&bin,&["sports","missing"]).unwrap().is_none());
+    for format in [Format::V1,Format::V2]{
+        for counted in [false,true]{
+            for checksum in [false,true]{
+                let options = EncodeOptions{format,header:true,counted,checksum,..EncodeOptions::default()};
+                let bin = doc.write_with(options);
+                let range = get_path_range(&bin,&["sports","match"]).unwrap().unwrap();
+                if format == Format::V2 || counted{
+                    assert!(DocValue::read(&bin[range]).is_err());
+                }
+                let line = get_path_bytes(&bin,&["sports","match"]).unwrap().unwrap();
+                let game_match = DocValue::read(&line).unwrap();
+                assert_eq!(game_match.as_object().unwrap().get("scores").unwrap().as_vec().unwrap().len(),4);
+                assert!(DocRef::read(&line).is_ok());
+                let score = get_path_bytes(&bin,&["sports","match","scores","0"]).unwrap().unwrap();
+                assert_eq!(DocValue::read(&score).unwrap().as_float().unwrap(),12.5);
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use gzbbinarydoc::{DocValue,DecodeError,get_path};
+use std::io::Cursor;
+
+fn doc()->DocValue{
+    let mut doc = DocValue::object();
+    doc.insert("name","akku");
+    doc
+}
+
+#[test]
+fn header_round_trip(){
+    let bin = doc().write_with_header();
+    assert_eq!(&bin[..4],b"GZBD");
+    assert_eq!(&bin[6..],&doc().write()[..]);
+    let rebuild = DocValue::read(&bin).unwrap();
+    assert!(rebuild.key_is_string("name"));
+    assert!(get_path(&bin,&["name"]).unwrap().is_some());
+    let mut stream = Cursor::new(bin);
+    assert!(DocValue::read_from(&mut stream).unwrap().key_is_string("name"));
+    assert!(matches!(DocValue::read_from(&mut stream),Err(DecodeError::Eof)));
+}
+
+#[test]
+fn rejects_unknown_version_and_flags(){
+    let mut bin = doc().write_with_header();
+    bin[4] = 200;
+    assert!(matches!(DocValue::read(&bin),Err(DecodeError::UnsupportedVersion{version:200,..})));
+    let mut bin = doc().write_with_header();
+    bin[5] = 0b1000_0000;
+    assert!(matches!(DocValue::read(&bin),Err(DecodeError::UnsupportedFlags{..})));
+}
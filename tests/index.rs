@@ -0,0 +1,58 @@
+use gzbbinarydoc::{doc,DocValue};
+
+fn person()->DocValue{
+    doc!({"name":"akku","sports":{"game":"cricket","match":{"scores":[12.5,15,null]}}})
+}
+
+#[test]
+fn index_reads_by_reference(){
+    let person = person();
+    assert_eq!(person["sports"]["match"]["scores"][1],doc!(15));
+    assert_eq!(person[&"name".to_string()],doc!("akku"));
+    assert!(person["missing"]["deeper"][3].self_is_null());
+    assert!(person["name"]["not a object"].self_is_null());
+    assert!(person["sports"]["match"]["scores"][9].self_is_null());
+    assert!(person[0].self_is_null());
+}
+
+#[test]
+fn index_mut_creates_objects(){
+    let mut person = person();
+    person["sports"]["match"]["scores"][2] = doc!(7);
+    person["sports"]["coach"]["name"] = doc!("king");
+    person["age"] = doc!(24);
+    assert_eq!(person["sports"]["match"]["scores"],doc!([12.5,15,7]));
+    assert_eq!(person["sports"]["coach"],doc!({"name":"king"}));
+    assert_eq!(person["age"],doc!(24));
+    let mut empty = DocValue::Null;
+    empty["a"]["b"] = doc!(true);
+    assert_eq!(empty,doc!({"a":{"b":true}}));
+}
+
+#[test]
+fn get_returns_references(){
+    let mut person = person();
+    assert_eq!(person.get("name"),Some(&doc!("akku")));
+    assert_eq!(person.get("missing"),None);
+    assert_eq!(person["sports"]["match"]["scores"].get_index(0),Some(&doc!(12.5)));
+    assert_eq!(person["sports"].get_index(0),None);
+    if let Some(name) = person.get_mut("name"){
+        *name = doc!("king");
+    }
+    assert_eq!(person["name"],doc!("king"));
+    assert!(person.get_index_mut(0).is_none());
+}
+
+#[test]
+#[should_panic(expected = "cannot index a string with key")]
+fn index_mut_on_wrong_type_panics(){
+    let mut person = person();
+    person["name"]["first"] = doc!("a");
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn index_mut_out_of_bounds_panics(){
+    let mut person = person();
+    person["sports"]["match"]["scores"][3] = doc!(1);
+}
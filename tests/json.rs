@@ -0,0 +1,75 @@
+use gzbbinarydoc::{doc,BinaryFormat,DecodeOptions,DocValue,JsonError,JsonOptions,Limit};
+
+fn sample()->DocValue{
+    doc!({
+        "name":"akku \"the\" king\n\u{1}é😀",
+        "age":24,
+        "score":15.0,
+        "tiny":1e-300,
+        "huge":-1e300,
+        "bin":bin[0,1,2,250,255],
+        "empty bin":bin[],
+        "nan":f64::NAN,
+        "inf":f64::INFINITY,
+        "network":null,
+        "flags":[true,false,[],{}],
+        "looks tagged":{"$binary":"AQID"},
+        "dollar keys":{"$ref":"#","$$x":1,"$float":{"$binary":bin[1]}},
+    })
+}
+
+#[test]
+fn round_trips_without_loss(){
+    let doc = sample();
+    for pretty in [false,true]{
+        let json = doc.to_json_with(JsonOptions{pretty,..JsonOptions::default()});
+        let back = DocValue::from_json(&json).unwrap();
+        assert_eq!(back.to_json(),doc.to_json());
+    }
+    let back = DocValue::from_json(&doc.to_json()).unwrap();
+    let map = back.as_object().unwrap();
+    assert!(matches!(map["age"],DocValue::Num(24)));
+    assert!(matches!(map["score"],DocValue::Float(v) if v == 15.0));
+    assert_eq!(map["bin"].as_binary().unwrap(),vec![0,1,2,250,255]);
+    assert!(matches!(map["nan"],DocValue::Float(v) if v.is_nan()));
+    assert_eq!(map["looks tagged"].as_object().unwrap()["$binary"].as_string().unwrap(),"AQID");
+    let dollar = map["dollar keys"].as_object().unwrap();
+    assert_eq!(dollar["$ref"].as_string().unwrap(),"#");
+    assert!(matches!(dollar["$$x"],DocValue::Num(1)));
+    assert!(matches!(&dollar["$float"].as_object().unwrap()["$binary"],DocValue::Binary(b) if b == &[1]));
+    assert!(DocValue::from_json(&doc!({"$binary":"AQID"}).to_json()).unwrap().as_object().is_some());
+    assert!(DocValue::from_json(r##"{"$ref":"#"}"##).unwrap().as_object().unwrap().contains_key("$ref"));
+}
+
+#[test]
+fn writes_json_text(){
+    let doc = doc!({"a":null,"b":[1,2.5,bin[1,2,3]]});
+    assert_eq!(doc.to_json(),r#"{"a":null,"b":[1,2.5,{"$binary":"AQID"}]}"#);
+    let plain = doc.to_json_with(JsonOptions{binary:BinaryFormat::Base64,..JsonOptions::default()});
+    assert_eq!(plain,r#"{"a":null,"b":[1,2.5,"AQID"]}"#);
+    let pretty = doc.to_json_with(JsonOptions{pretty:true,..JsonOptions::default()});
+    assert_eq!(pretty,"{\n  \"a\": null,\n  \"b\": [\n    1,\n    2.5,\n    {\n      \"$binary\": \"AQID\"\n    }\n  ]\n}");
+}
+
+#[test]
+fn reads_any_json(){
+    let doc = DocValue::from_json(" {\"a\" : [ -0 , 1E2, \"\\ud83d\\ude00\\/\" ], \"big\":18446744073709551616 } ").unwrap();
+    let map = doc.as_object().unwrap();
+    let pool = map["a"].as_vec().unwrap();
+    assert!(matches!(pool[0],DocValue::Num(0)));
+    assert!(matches!(pool[1],DocValue::Float(v) if v == 100.0));
+    assert_eq!(pool[2].as_string().unwrap(),"😀/");
+    assert!(matches!(map["big"],DocValue::Float(_)));
+}
+
+#[test]
+fn json_errors(){
+    for (text,offset) in [("",0),("[1,]",3),("{\"a\" 1}",5),("01",0),("1.",0),("\"\\x\"",1),("\"\\ud800\"",1),("[1] x",4),("{\"$binary\":\"A\"}",0),("{\"$float\":1}",0),("tru",0)]{
+        let err = DocValue::from_json(text).unwrap_err();
+        assert_eq!(err.offset(),offset,"{} {:?}",text,err);
+    }
+    assert!(matches!(DocValue::from_json("[1] x"),Err(JsonError::TrailingCharacters{offset:4})));
+    let options = DecodeOptions{max_depth:2,..DecodeOptions::default()};
+    assert!(DocValue::from_json_with("[[1]]",options).is_ok());
+    assert!(matches!(DocValue::from_json_with("[[[1]]]",options),Err(JsonError::LimitExceeded{limit:Limit::Depth,..})));
+}
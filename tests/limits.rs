@@ -0,0 +1,61 @@
+use gzbbinarydoc::{DocValue,DecodeError,DecodeOptions,Limit};
+
+fn nested(depth:usize)->DocValue{
+    let mut doc = DocValue::vec();
+    doc.push(true);
+    for _ in 1..depth{
+        let mut parent = DocValue::vec();
+        parent.push(doc);
+        doc = parent;
+    }
+    doc
+}
+
+#[test]
+fn depth_limit(){
+    let bin = nested(10).write();
+    let options = DecodeOptions{max_depth:10,..DecodeOptions::default()};
+    assert!(DocValue::read_with(&bin,options).is_ok());
+    let options = DecodeOptions{max_depth:9,..DecodeOptions::default()};
+    assert!(matches!(
+        DocValue::read_with(&bin,options),
+        Err(DecodeError::LimitExceeded{limit:Limit::Depth,..})
+    ));
+}
+
+#[test]
+fn default_depth_limit(){
+    let bin = nested(1000).write();
+    assert!(matches!(
+        DocValue::read(&bin),
+        Err(DecodeError::LimitExceeded{limit:Limit::Depth,..})
+    ));
+}
+
+#[test]
+fn length_limit(){
+    let mut doc = DocValue::object();
+    doc.insert("id","akku");
+    let bin = doc.write();
+    let options = DecodeOptions{max_len:3,..DecodeOptions::default()};
+    let error = DocValue::read_with(&bin,options).unwrap_err();
+    assert!(matches!(error,DecodeError::LimitExceeded{limit:Limit::Length,..}));
+    assert_eq!(error.path(),"id");
+}
+
+#[test]
+fn entries_and_total_limit(){
+    let mut doc = DocValue::vec();
+    for i in 0..10{doc.push(i as i64);}
+    let bin = doc.write();
+    let options = DecodeOptions{max_entries:9,..DecodeOptions::default()};
+    assert!(matches!(
+        DocValue::read_with(&bin,options),
+        Err(DecodeError::LimitExceeded{limit:Limit::Entries,..})
+    ));
+    let options = DecodeOptions{max_total_bytes:bin.len() - 1,..DecodeOptions::default()};
+    assert!(matches!(
+        DocValue::read_with(&bin,options),
+        Err(DecodeError::LimitExceeded{limit:Limit::TotalBytes,..})
+    ));
+}
@@ -0,0 +1,49 @@
+use gzbbinarydoc::{DocValue,DecodeError};
+
+mod common;
+use common::person;
+
+#[test]
+fn empty_input(){
+    assert!(matches!(DocValue::read(&[]),Err(DecodeError::Truncated{offset:0,..})));
+}
+
+#[test]
+fn every_truncation_is_an_error(){
+    let bin = person().write();
+    for len in 0..bin.len(){
+        assert!(DocValue::read(&bin[..len]).is_err());
+    }
+}
+
+#[test]
+fn trailing_bytes_after_root(){
+    let mut bin = person().write();
+    bin.push(0);
+    assert!(matches!(DocValue::read(&bin),Err(DecodeError::TrailingBytes{..})));
+}
+
+#[test]
+fn child_can_not_read_past_parent(){
+
+    // a vec with one entry holding true, the entry frame says 10 bytes
+    // but the inner data line claims 2 bytes of bool data
+    let mut bin = vec![1];
+    bin.extend_from_slice(&20u64.to_be_bytes());
+    bin.extend_from_slice(&10u64.to_be_bytes());
+    bin.push(6);
+    bin.extend_from_slice(&2u64.to_be_bytes());
+    bin.push(1);
+    bin.push(0);
+    bin.push(0);
+
+    assert!(matches!(DocValue::read(&bin),Err(DecodeError::Truncated{..})));
+
+}
+
+#[test]
+fn huge_length_prefix(){
+    let mut bin = vec![3];
+    bin.extend_from_slice(&u64::MAX.to_be_bytes());
+    assert!(DocValue::read(&bin).is_err());
+}
@@ -0,0 +1,90 @@
+use gzbbinarydoc::{doc,DocValue,FromDocValue,Map,ToDocValue,TryToDocValue};
+
+fn sample()->Map<String,i64>{
+    [("c",3),("a",1),("b",2)].into_iter().map(|(k,v)|(k.to_string(),v)).collect()
+}
+
+fn sorted<'a>(keys:impl Iterator<Item = &'a String>)->Vec<&'a str>{
+    let mut keys:Vec<&str> = keys.map(|k|k.as_str()).collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn same_api_with_and_without_preserve_order(){
+    let mut map = sample();
+    assert_eq!(map.len(),3);
+    assert!(!map.is_empty());
+    assert_eq!(map.get("a"),Some(&1));
+    assert_eq!(map["b"],2);
+    assert!(map.contains_key("c"));
+    assert_eq!(map.get_key_value("c"),Some((&"c".to_string(),&3)));
+    map["b"] += 10;
+    *map.get_mut("c").unwrap() += 10;
+    for value in map.values_mut(){
+        *value += 1;
+    }
+    for (_,value) in &mut map{
+        *value *= 2;
+    }
+    assert_eq!(map.insert("d".to_string(),0),None);
+    assert_eq!(map.insert("d".to_string(),8),Some(0));
+    assert_eq!(sorted(map.keys()),["a","b","c","d"]);
+    assert_eq!(map.values().sum::<i64>(),4 + 26 + 28 + 8);
+    assert_eq!(map.iter().len(),4);
+    assert_eq!(map.remove("a"),Some(4));
+    assert_eq!(map.remove_entry("d"),Some(("d".to_string(),8)));
+    assert_eq!(map.remove("a"),None);
+    map.retain(|_,v|*v > 26);
+    assert_eq!(sorted(map.keys()),["c"]);
+    map.extend([("e".to_string(),5)]);
+    let owned:Vec<(String,i64)> = {
+        let mut v:Vec<_> = map.clone().into_iter().collect();
+        v.sort();
+        v
+    };
+    assert_eq!(owned,[("c".to_string(),28),("e".to_string(),5)]);
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map,Map::default());
+    assert_eq!(format!("{:?}",sample().into_iter().filter(|(k,_)|k == "a").collect::<Map<_,_>>()),r#"{"a": 1}"#);
+}
+
+#[test]
+fn equality_ignores_order(){
+    let mut forward:Map<String,i64> = Map::new();
+    let mut backward:Map<String,i64> = Map::with_capacity(3);
+    for key in ["x","y","z"]{
+        forward.insert(key.to_string(),1);
+    }
+    for key in ["z","y","x"]{
+        backward.insert(key.to_string(),1);
+    }
+    assert_eq!(forward,backward);
+    backward.insert("x".to_string(),2);
+    assert_ne!(forward,backward);
+}
+
+#[test]
+fn converts_to_and_from_doc(){
+    let map = sample();
+    let doc = map.toDocValue();
+    assert_eq!(doc,doc!({"a":1,"b":2,"c":3}));
+    assert_eq!(map.tryToDocValue().unwrap(),doc);
+    assert_eq!(Map::<String,i64>::fromDocValue(&doc).unwrap(),map);
+    assert_eq!(Map::<String,i64>::try_from(&doc).unwrap(),map);
+    let object:Map = doc.as_object().unwrap();
+    assert_eq!(object["c"],DocValue::Num(3));
+    assert!(Map::<String,i64>::fromDocValue(&DocValue::Num(1)).is_err());
+}
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn keeps_insertion_order_with_preserve_order(){
+    let mut map = sample();
+    assert_eq!(map.keys().collect::<Vec<_>>(),["c","a","b"]);
+    map.remove("c");
+    map.insert("c".to_string(),3);
+    map.insert("a".to_string(),0);
+    assert_eq!(map.iter().map(|(k,v)|(k.as_str(),*v)).collect::<Vec<_>>(),[("a",0),("b",2),("c",3)]);
+}
@@ -0,0 +1,63 @@
+use gzbbinarydoc::{doc,DocValue,PointerError};
+
+fn rfc_sample()->DocValue{
+    doc!({
+        "foo":["bar","baz"],
+        "":0,
+        "a/b":1,
+        "c%d":2,
+        "e^f":3,
+        "g|h":4,
+        "i\\j":5,
+        "k\"l":6,
+        " ":7,
+        "m~n":8,
+    })
+}
+
+#[test]
+fn rfc_6901_examples(){
+    let doc = rfc_sample();
+    assert_eq!(doc.pointer(""),Some(&doc));
+    assert_eq!(doc.pointer("/foo"),Some(&doc!(["bar","baz"])));
+    assert_eq!(doc.pointer("/foo/0"),Some(&doc!("bar")));
+    let expected = [("/",0),("/a~1b",1),("/c%d",2),("/e^f",3),("/g|h",4),("/i\\j",5),("/k\"l",6),("/ ",7),("/m~0n",8)];
+    for (pointer,value) in expected{
+        assert_eq!(doc.pointer(pointer),Some(&doc!(value)),"{}",pointer);
+    }
+}
+
+#[test]
+fn invalid_and_missing_pointers(){
+    let doc = rfc_sample();
+    for pointer in ["foo","/foo/01","/foo/-","/foo/2","/foo/+1","/m~2n","/m~","/missing","/foo/0/deeper"]{
+        assert_eq!(doc.pointer(pointer),None,"{}",pointer);
+    }
+}
+
+#[test]
+fn pointer_mut_set_and_remove(){
+    let mut doc = doc!({"sports":{"match":{"scores":[12.5,15]}}});
+    *doc.pointer_mut("/sports/match/scores/1").unwrap() = doc!(16);
+    assert_eq!(doc.pointer_set("/sports/match/scores/0",1.5),Ok(Some(doc!(12.5))));
+    assert_eq!(doc.pointer_set("/sports/match/scores/2",()),Ok(None));
+    assert_eq!(doc.pointer_set("/sports/match/scores/-",true),Ok(None));
+    assert_eq!(doc.pointer_set("/sports/game","cricket"),Ok(None));
+    assert_eq!(doc.pointer_set("/sports/a~1b",1),Ok(None));
+    assert_eq!(doc,doc!({"sports":{"game":"cricket","a/b":1,"match":{"scores":[1.5,16,null,true]}}}));
+
+    assert_eq!(doc.pointer_set("sports",1),Err(PointerError::Invalid{pointer:"sports".to_string()}));
+    assert_eq!(doc.pointer_set("/coach/name","king"),Err(PointerError::NotFound{path:"/coach".to_string()}));
+    assert_eq!(doc.pointer_set("/sports/game/x",1),Err(PointerError::NotContainer{path:"/sports/game".to_string()}));
+    assert_eq!(doc.pointer_set("/sports/match/scores/9",1),Err(PointerError::InvalidIndex{path:"/sports/match/scores/9".to_string()}));
+
+    assert_eq!(doc.pointer_remove("/sports/match/scores/1"),Some(doc!(16)));
+    assert_eq!(doc.pointer_remove("/sports/match/scores/-"),None);
+    assert_eq!(doc.pointer_remove("/sports/a~1b"),Some(doc!(1)));
+    assert_eq!(doc.pointer_remove("/sports/missing"),None);
+    assert_eq!(doc.pointer_remove(""),None);
+    assert_eq!(doc,doc!({"sports":{"game":"cricket","match":{"scores":[1.5,null,true]}}}));
+
+    assert_eq!(doc.pointer_set("",doc!([1])),Ok(Some(doc!({"sports":{"game":"cricket","match":{"scores":[1.5,null,true]}}}))));
+    assert_eq!(doc,doc!([1]));
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "preserve_order")]
+
+use gzbbinarydoc::{doc,DocRef,DocValue,EncodeOptions,Format,Map};
+
+const KEYS:[&str;6] = ["zebra","apple","mango","kiwi","banana","cherry"];
+
+fn sample()->DocValue{
+    let mut doc = DocValue::object();
+    for (index,key) in KEYS.iter().enumerate(){
+        doc.insert(key,index as i64);
+    }
+    doc
+}
+
+fn keys(doc:&mut DocValue)->Vec<String>{
+    doc.object_iter().unwrap().map(|(k,_)|k.clone()).collect()
+}
+
+#[test]
+fn insertion_order_survives_write_and_read(){
+    let mut doc = sample();
+    assert_eq!(keys(&mut doc),KEYS);
+    for format in [Format::V1,Format::V2]{
+        for counted in [false,true]{
+            let bin = doc.write_with(EncodeOptions{format,counted,..EncodeOptions::default()});
+            let mut back = DocValue::read(&bin).unwrap();
+            assert_eq!(keys(&mut back),KEYS);
+            let view = DocRef::read(&bin).unwrap();
+            let view_keys:Vec<&str> = view.as_object().unwrap().keys().copied().collect();
+            assert_eq!(view_keys,KEYS);
+        }
+    }
+    let mut sorted = DocValue::read(&doc.write_canonical()).unwrap();
+    let mut expected = KEYS.to_vec();
+    expected.sort();
+    assert_eq!(keys(&mut sorted),expected);
+}
+
+#[test]
+fn insertion_order_in_json_and_remove(){
+    let mut doc = sample();
+    let json = doc.to_json();
+    assert_eq!(json,r#"{"zebra":0,"apple":1,"mango":2,"kiwi":3,"banana":4,"cherry":5}"#);
+    assert_eq!(keys(&mut DocValue::from_json(&json).unwrap()),KEYS);
+    doc.object_remove("mango");
+    assert_eq!(keys(&mut doc),["zebra","apple","kiwi","banana","cherry"]);
+    let built = doc!({"b":1,"a":{"y":2,"x":3}});
+    let map:Map = built.as_object().unwrap();
+    assert_eq!(map.keys().collect::<Vec<_>>(),["b","a"]);
+    assert_eq!(map["a"].as_object().unwrap().keys().collect::<Vec<_>>(),["y","x"]);
+}
@@ -0,0 +1,49 @@
+use gzbbinarydoc::{DocValue,DecodeError,DecodeOptions,Limit};
+use std::io::{Cursor,Read};
+
+fn doc(name:&str)->DocValue{
+    let mut doc = DocValue::object();
+    doc.insert("name",name);
+    doc
+}
+
+#[test]
+fn reads_one_document_at_a_time(){
+    let mut bin = doc("akku").write();
+    bin.extend(doc("king").write());
+    bin.push(42);
+    let mut stream = Cursor::new(bin);
+    let first = DocValue::read_from(&mut stream).unwrap();
+    assert_eq!(first.as_object().unwrap().get("name").unwrap().as_string().unwrap(),"akku");
+    let second = DocValue::read_from(&mut stream).unwrap();
+    assert_eq!(second.as_object().unwrap().get("name").unwrap().as_string().unwrap(),"king");
+    let mut rest = vec![];
+    stream.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest,vec![42]);
+}
+
+#[test]
+fn eof_and_truncated_are_distinct(){
+    let bin = doc("akku").write();
+    let mut stream = Cursor::new(bin.clone());
+    DocValue::read_from(&mut stream).unwrap();
+    assert!(matches!(DocValue::read_from(&mut stream),Err(DecodeError::Eof)));
+    for len in 1..bin.len(){
+        let mut stream = Cursor::new(&bin[..len]);
+        assert!(matches!(DocValue::read_from(&mut stream),Err(DecodeError::Truncated{..})));
+    }
+}
+
+#[test]
+fn malformed_and_oversized(){
+    let mut bin = doc("akku").write();
+    bin[0] = 9;
+    assert!(matches!(DocValue::read_from(&mut Cursor::new(bin)),Err(DecodeError::UnknownType{tag:9,..})));
+    let mut bin = vec![2];
+    bin.extend_from_slice(&u64::MAX.to_be_bytes());
+    let options = DecodeOptions{max_total_bytes:1024,..DecodeOptions::default()};
+    assert!(matches!(
+        DocValue::read_from_with(&mut Cursor::new(bin),options),
+        Err(DecodeError::LimitExceeded{limit:Limit::TotalBytes,..})
+    ));
+}
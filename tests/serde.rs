@@ -0,0 +1,158 @@
+#![cfg(feature = "serde")]
+
+use gzbbinarydoc::{DocValue,EncodeOptions,Format,SerdeError,from_bytes,from_bytes_owned,to_bytes};
+use serde::{Serialize,Deserialize};
+use std::collections::{BTreeMap,HashMap};
+
+mod common;
+
+#[derive(Serialize,Deserialize,Debug,PartialEq)]
+enum Shape{
+    Empty,
+    Circle(f64),
+    Line(i32,i32),
+    Rect{width:u32,height:u32},
+}
+
+#[derive(Serialize,Deserialize,Debug,PartialEq)]
+struct Game{
+    game:String,
+    scores:Vec<f64>,
+}
+
+#[derive(Serialize,Deserialize,Debug,PartialEq)]
+struct Person{
+    name:String,
+    age:u8,
+    king:bool,
+    initial:char,
+    network:Option<String>,
+    nick:Option<String>,
+    #[serde(with = "serde_bytes")]
+    bin:Vec<u8>,
+    sports:Game,
+    shapes:Vec<Shape>,
+    ratings:HashMap<String,i32>,
+    levels:BTreeMap<u32,String>,
+    pair:(i64,String),
+    unit:(),
+}
+
+fn person()->Person{
+    Person{
+        name:"akku".to_string(),
+        age:24,
+        king:true,
+        initial:'a',
+        network:None,
+        nick:Some("gzb".to_string()),
+        bin:vec![1,2,3],
+        sports:Game{game:"cricket".to_string(),scores:vec![12.5,15.0]},
+        shapes:vec![Shape::Empty,Shape::Circle(1.5),Shape::Line(-1,2),Shape::Rect{width:3,height:4}],
+        ratings:HashMap::from([("chess".to_string(),1200)]),
+        levels:BTreeMap::from([(1,"one".to_string()),(20,"twenty".to_string())]),
+        pair:(-7,"seven".to_string()),
+        unit:(),
+    }
+}
+
+#[test]
+fn serde_round_trip(){
+    let bin = to_bytes(&person()).unwrap();
+    assert_eq!(from_bytes::<Person>(&bin).unwrap(),person());
+}
+
+#[test]
+fn matches_the_document_model(){
+
+    let bin = to_bytes(&person()).unwrap();
+    let doc = DocValue::read(&bin).unwrap();
+    assert!(doc.key_is_binary("bin"));
+    assert!(doc.key_is_null("network"));
+    assert!(doc.key_is_num("age"));
+    let shapes = doc.as_object().unwrap().get("shapes").unwrap().as_vec().unwrap();
+    assert_eq!(shapes[0].as_string().unwrap(),"Empty");
+    assert_eq!(shapes[1].as_object().unwrap().get("Circle").unwrap().as_float().unwrap(),1.5);
+
+    let mut scores = DocValue::vec();
+    scores.push(12.5);
+    scores.push(15.0);
+    let mut game = DocValue::object();
+    game.insert("scores",scores);
+    assert_eq!(to_bytes(&HashMap::from([("scores",vec![12.5,15.0])])).unwrap(),game.write());
+
+    for options in [EncodeOptions::default(),EncodeOptions{format:Format::V2,counted:true,checksum:true,..EncodeOptions::default()}]{
+        let bin = game.write_with(options);
+        let read:HashMap<String,Vec<f64>> = from_bytes(&bin).unwrap();
+        assert_eq!(read["scores"],vec![12.5,15.0]);
+    }
+
+}
+
+#[test]
+fn borrows_from_input(){
+    let bin = to_bytes(&("akku",vec!["cricket"])).unwrap();
+    let (name,games):(&str,Vec<&str>) = from_bytes(&bin).unwrap();
+    assert_eq!(name,"akku");
+    assert_eq!(games,["cricket"]);
+}
+
+#[test]
+fn serde_errors(){
+    assert!(matches!(to_bytes(&u64::MAX),Err(SerdeError::Message(_))));
+    assert!(matches!(to_bytes(&HashMap::from([(vec![1],1)])),Err(SerdeError::KeyNotString)));
+    let bin = to_bytes(&person()).unwrap();
+    assert!(matches!(from_bytes::<Game>(&bin),Err(SerdeError::Message(_))));
+    assert!(matches!(from_bytes::<(i64,i64)>(&to_bytes(&(1,2,3)).unwrap()),Err(SerdeError::Decode(_))));
+    for len in 0..bin.len(){
+        assert!(from_bytes::<Person>(&bin[..len]).is_err());
+    }
+}
+
+#[test]
+fn owned_reads_plain_documents(){
+    let bin = to_bytes(&person()).unwrap();
+    assert_eq!(from_bytes_owned::<Person>(&bin).unwrap(),person());
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn owned_reads_compressed_documents(){
+    let doc = DocValue::read(&to_bytes(&person()).unwrap()).unwrap();
+    for format in [Format::V1,Format::V2]{
+        let bin = doc.write_with(EncodeOptions{format,compression:Some(0),..EncodeOptions::default()});
+        assert!(matches!(from_bytes::<Person>(&bin),Err(SerdeError::Decode(gzbbinarydoc::DecodeError::Compressed{..}))));
+        assert_eq!(from_bytes_owned::<Person>(&bin).unwrap(),person());
+    }
+}
+
+///scores of the shared person fixture
+fn scores(doc:&DocValue)->Vec<DocValue>{
+    let sports = doc.as_object().unwrap().get("sports").unwrap().clone();
+    let game_match = sports.as_object().unwrap().get("match").unwrap().clone();
+    game_match.as_object().unwrap().get("scores").unwrap().as_vec().unwrap()
+}
+
+#[test]
+fn doc_value_through_json(){
+    let json = serde_json::to_string(&common::person()).unwrap();
+    let doc:DocValue = serde_json::from_str(&json).unwrap();
+    assert_eq!(doc.as_object().unwrap().get("name").unwrap().as_string().unwrap(),"akku");
+    assert!(doc.key_is_bool("king"));
+    let scores = scores(&doc);
+    assert_eq!(scores[0].as_float().unwrap(),12.5);
+    assert_eq!(scores[1].as_num().unwrap(),15);
+    assert!(scores[2].self_is_null());
+    assert!(serde_json::from_str::<DocValue>("18446744073709551615").is_err());
+}
+
+#[test]
+fn doc_value_through_to_bytes(){
+    let doc = common::person();
+    let bin = to_bytes(&doc).unwrap();
+    let read = DocValue::read(&bin).unwrap();
+    assert_eq!(read.as_object().unwrap().get("bin").unwrap().as_binary().unwrap(),vec![1,2,3]);
+    let read:DocValue = from_bytes(&doc.write()).unwrap();
+    assert_eq!(read.as_object().unwrap().get("bin").unwrap().as_binary().unwrap(),vec![1,2,3]);
+    assert_eq!(scores(&read).len(),4);
+}
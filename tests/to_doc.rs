@@ -0,0 +1,132 @@
+use gzbbinarydoc::{DocValue,FromDocValue,ToDocError};
+use std::collections::{BTreeMap,HashMap};
+
+#[test]
+fn integer_float_and_collection_types(){
+    let mut doc = DocValue::object();
+    doc.insert("count",5u32);
+    doc.insert("small",-3i8);
+    doc.try_insert("big",u64::from(u32::MAX) + 1).unwrap();
+    doc.insert("ratio",0.5f32);
+    doc.insert("tags",vec!["a","b"]);
+    doc.insert("slice",&[1i32,2][..]);
+    doc.insert("array",[true,false]);
+    doc.insert("bin",vec![1u8,2,3]);
+    doc.insert("bin_array",[4u8,5]);
+    doc.insert("missing",None::<i64>);
+    doc.insert("some",Some("x"));
+    doc.insert("scores",HashMap::from([("a".to_string(),1.5)]));
+    doc.insert("sorted",BTreeMap::from([("b".to_string(),vec![1u16])]));
+    let name = String::from("akku");
+    doc.insert("name",&name);
+
+    let back:HashMap<String,DocValue> = HashMap::fromDocValue(&DocValue::read(&doc.write()).unwrap()).unwrap();
+    assert_eq!(u32::fromDocValue(&back["count"]).unwrap(),5);
+    assert_eq!(i8::fromDocValue(&back["small"]).unwrap(),-3);
+    assert_eq!(u64::fromDocValue(&back["big"]).unwrap(),u64::from(u32::MAX) + 1);
+    assert_eq!(f32::fromDocValue(&back["ratio"]).unwrap(),0.5);
+    assert_eq!(Vec::<String>::fromDocValue(&back["tags"]).unwrap(),vec!["a","b"]);
+    assert_eq!(Vec::<i32>::fromDocValue(&back["slice"]).unwrap(),vec![1,2]);
+    assert_eq!(Vec::<bool>::fromDocValue(&back["array"]).unwrap(),vec![true,false]);
+    assert_eq!(back["bin"].as_binary().unwrap(),vec![1,2,3]);
+    assert_eq!(back["bin_array"].as_binary().unwrap(),vec![4,5]);
+    assert!(matches!(back["missing"],DocValue::Null));
+    assert_eq!(String::fromDocValue(&back["some"]).unwrap(),"x");
+    assert_eq!(HashMap::<String,f64>::fromDocValue(&back["scores"]).unwrap()["a"],1.5);
+    assert_eq!(BTreeMap::<String,Vec<u16>>::fromDocValue(&back["sorted"]).unwrap()["b"],vec![1]);
+    assert_eq!(String::fromDocValue(&back["name"]).unwrap(),"akku");
+}
+
+#[test]
+fn untyped_integer_vec_is_not_binary(){
+    let mut doc = DocValue::vec();
+    doc.push(vec![1,2,3]);
+    doc.push(vec![1u8,2,3]);
+    assert!(matches!(&doc[0],DocValue::Vec(pool) if pool.len() == 3 && matches!(pool[0],DocValue::Num(1))));
+    assert!(matches!(&doc[1],DocValue::Binary(bin) if bin == &[1,2,3]));
+}
+
+#[test]
+fn wide_integers_are_fallible(){
+    let mut doc = DocValue::vec();
+    doc.try_push(7usize).unwrap();
+    doc.try_push(-3i128).unwrap();
+    doc.try_push("still fine").unwrap();
+    let err = doc.try_push(u64::MAX).unwrap_err();
+    assert_eq!(err,ToDocError::new("u64","18446744073709551615"));
+    assert_eq!(err.to_string(),"u64 18446744073709551615 does not fit in a num");
+    assert!(doc.try_push(i128::MIN).is_err());
+    assert_eq!(doc.as_vec().unwrap().len(),3);
+    let mut map = DocValue::object();
+    assert!(map.try_insert("big",u128::MAX).is_err());
+    assert!(!map.contains_key("big").unwrap());
+    assert!(matches!(DocValue::try_from(42u64),Ok(DocValue::Num(42))));
+    assert!(DocValue::try_from(usize::MAX).is_err());
+}
+
+#[test]
+fn wide_integer_options_and_references(){
+    let pool = ["a","b"];
+    let mut doc = DocValue::object();
+    doc.try_insert("n",Some(pool.len())).unwrap();
+    doc.try_insert("none",None::<u64>).unwrap();
+    let ids = [7u64,u64::MAX];
+    doc.try_insert("ref",ids.iter().take(1).collect::<Vec<&u64>>()).unwrap();
+    doc.try_insert("boxed",Box::new(8u128)).unwrap();
+    assert_eq!(doc.as_object().unwrap().get("n").unwrap().as_num().unwrap(),2);
+    assert!(doc.key_is_null("none"));
+    assert!(doc.key_is_vec("ref"));
+    assert!(doc.key_is_num("boxed"));
+    assert!(doc.try_insert("big",Some(u64::MAX)).is_err());
+    assert!(doc.try_insert("big",ids.iter().collect::<Vec<&u64>>()).is_err());
+    assert!(!doc.contains_key("big").unwrap());
+}
+
+#[test]
+fn wide_integer_vecs_slices_and_arrays(){
+    let mut doc = DocValue::object();
+    doc.try_insert("ids",vec![1u64,2,3]).unwrap();
+    doc.try_insert("slice",&[4u64,5][..]).unwrap();
+    doc.try_insert("array",[6usize;3]).unwrap();
+    doc.try_insert("nested",vec![vec![7i128],vec![]]).unwrap();
+    for key in ["ids","slice","array"]{
+        let pool = doc.as_object().unwrap().get(key).unwrap().as_vec().unwrap();
+        assert!(pool.iter().all(|v|v.self_is_num()));
+    }
+    assert_eq!(doc.as_object().unwrap().get("array").unwrap().as_vec().unwrap().len(),3);
+    assert_eq!(doc.as_object().unwrap().get("nested").unwrap().as_vec().unwrap().len(),2);
+    let err = doc.try_insert("big",vec![1,u64::MAX]).unwrap_err();
+    assert_eq!(err,ToDocError::new("u64","18446744073709551615"));
+    assert!(doc.try_insert("big",[usize::MAX]).is_err());
+    assert!(!doc.contains_key("big").unwrap());
+    doc.try_insert("bin",vec![1u8,2,3]).unwrap();
+    doc.try_insert("bin slice",&[1u8][..]).unwrap();
+    assert!(doc.key_is_binary("bin"));
+    assert!(doc.key_is_binary("bin slice"));
+}
+
+#[test]
+fn wide_integer_maps(){
+    let mut doc = DocValue::object();
+    doc.try_insert("hash",HashMap::from([("a".to_string(),1u64)])).unwrap();
+    doc.try_insert("tree",BTreeMap::from([("b".to_string(),vec![2usize])])).unwrap();
+    assert!(doc.as_object().unwrap().get("hash").unwrap().key_is_num("a"));
+    assert!(doc.as_object().unwrap().get("tree").unwrap().key_is_vec("b"));
+    assert!(doc.try_insert("big",HashMap::from([("a".to_string(),u128::MAX)])).is_err());
+    assert!(doc.try_insert("big",BTreeMap::from([("a".to_string(),i128::MAX)])).is_err());
+    assert!(!doc.contains_key("big").unwrap());
+}
+
+#[test]
+fn tuples(){
+    let mut doc = DocValue::object();
+    doc.insert("pair",("akku",24));
+    doc.try_insert("wide",(1u64,"x",Some(2usize))).unwrap();
+    let pair = doc.as_object().unwrap().get("pair").unwrap().as_vec().unwrap();
+    assert_eq!(pair[0].as_string().unwrap(),"akku");
+    assert_eq!(pair[1].as_num().unwrap(),24);
+    assert_eq!(doc.as_object().unwrap().get("wide").unwrap().as_vec().unwrap().len(),3);
+    assert!(doc.try_insert("big",(1,u64::MAX)).is_err());
+    let back:(String,i64) = <(String,i64)>::fromDocValue(doc.as_object().unwrap().get("pair").unwrap()).unwrap();
+    assert_eq!(back,("akku".to_string(),24));
+}
@@ -0,0 +1,34 @@
+use gzbbinarydoc::DocValue;
+use std::io::{self,Write};
+
+mod common;
+use common::person;
+
+#[test]
+fn same_bytes_as_write(){
+    let mut doc = person();
+    doc.insert("tags",DocValue::vec());
+    doc.insert("meta",DocValue::object());
+    let mut out = vec![];
+    doc.write_to(&mut out).unwrap();
+    assert_eq!(out,doc.write());
+}
+
+struct FailAfter(usize);
+
+impl Write for FailAfter{
+    fn write(&mut self,buf:&[u8])->io::Result<usize>{
+        if self.0 < buf.len(){
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe,"closed"));
+        }
+        self.0 -= buf.len();
+        Ok(buf.len())
+    }
+    fn flush(&mut self)->io::Result<()>{Ok(())}
+}
+
+#[test]
+fn propagates_io_errors(){
+    let error = person().write_to(&mut FailAfter(20)).unwrap_err();
+    assert_eq!(error.kind(),io::ErrorKind::BrokenPipe);
+}